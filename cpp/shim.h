@@ -1,9 +1,33 @@
+#include <exception>
 #include <memory>
+#include <sstream>
+#include <string>
 #include <xapian.h>
 
 #ifndef _XAPIAN_SHIM_H
 #define _XAPIAN_SHIM_H
 
+namespace rust {
+  namespace behavior {
+    // Xapian::Error does not derive from std::exception, so the default cxx handler would let it
+    // escape. Encode the exception's type, message, context and error string as a \x1f-separated
+    // record, which is decoded on the Rust side by `xapian_rs::Error`
+    template <typename Try, typename Fail>
+    static void trycatch(Try &&func, Fail &&fail) noexcept try {
+      func();
+    } catch (const Xapian::Error &e) {
+      const char *error_string = e.get_error_string();
+      std::string record = std::string(e.get_type()) + '\x1f' + e.get_msg() + '\x1f' + e.get_context() + '\x1f' + (error_string ? error_string : "");
+      fail(record.c_str());
+    } catch (const std::exception &e) {
+      std::string record = std::string("\x1f") + e.what() + "\x1f\x1f";
+      fail(record.c_str());
+    } catch (...) {
+      fail("\x1f" "unknown C++ exception\x1f\x1f");
+    }
+  }
+}
+
 namespace shim {
   enum WildcardLimitBehavior {
     WILDCARD_LIMIT_ERROR = Xapian::Query::WILDCARD_LIMIT_ERROR,
@@ -11,6 +35,18 @@ namespace shim {
     WILDCARD_LIMIT_MOST_FREQUENT = Xapian::Query::WILDCARD_LIMIT_MOST_FREQUENT,
   };
 
+  class FfiCompactor : public Xapian::Compactor {
+    public:
+      FfiCompactor() : Xapian::Compactor() {}
+      virtual FfiCompactor* upcast() { return this; }
+      virtual void set_status(const std::string &table, const std::string &status) override { this->report_status(table, status); }
+      virtual std::string resolve_duplicate_metadata(const std::string &key, size_t num_tags, const std::string tags[]) override {
+        return *(this->resolve_metadata(key, std::vector<std::string>(tags, tags + num_tags)));
+      }
+      virtual void report_status(const std::string&, const std::string&) = 0;
+      virtual std::unique_ptr<std::string> resolve_metadata(const std::string&, const std::vector<std::string>&) = 0;
+  };
+
   class FfiExpandDecider : public Xapian::ExpandDecider {
     public:
       FfiExpandDecider() : Xapian::ExpandDecider() {}
@@ -58,21 +94,16 @@ namespace shim {
   };
 
   inline Xapian::Database database_clone(const Xapian::Database &db) { return Xapian::Database(db); }
+  inline Xapian::Database database_empty() { return Xapian::Database(); }
+  inline std::size_t database_size(const Xapian::Database &db) { return db.size(); }
 
   inline Xapian::RangeProcessor& date_range_processor_upcast(Xapian::DateRangeProcessor &rp) { return rp; }
 
   inline Xapian::Document document_copy(const Xapian::Document &doc) { return Xapian::Document(doc); }
 
   inline void enquire_add_matchspy( Xapian::Enquire &e, FfiMatchSpy *m) { e.add_matchspy(m); }
-  inline Xapian::ESet enquire_get_eset(
-      const Xapian::Enquire &e, Xapian::termcount maxitems, const Xapian::RSet &rset,
-      int flags, const FfiExpandDecider *decider, double min_wt
-  ) { return e.get_eset(maxitems, rset, flags, decider, min_wt); }
-  inline Xapian::MSet enquire_get_mset(
-      const Xapian::Enquire &e, Xapian::doccount first,
-      Xapian::doccount maxitems,  Xapian::doccount atleast,
-      const Xapian::RSet *rset, const FfiMatchDecider *decider
-  ) { return e.get_mset(first, maxitems, atleast, rset, decider); }
+
+  inline Xapian::ESet eset_copy(const Xapian::ESet &eset) { return Xapian::ESet(eset); }
 
   inline Xapian::ESetIterator eset_iterator_copy(const Xapian::ESetIterator &it) { return Xapian::ESetIterator(it); }
   inline void eset_iterator_decrement(Xapian::ESetIterator &it) { it--; }
@@ -80,6 +111,8 @@ namespace shim {
   inline void eset_iterator_increment(Xapian::ESetIterator &it) { it++; }
   inline std::string eset_iterator_term(const Xapian::ESetIterator &it) { return *it; }
 
+  inline Xapian::MSet mset_copy(const Xapian::MSet &mset) { return Xapian::MSet(mset); }
+
   inline Xapian::MSetIterator mset_iterator_copy(const Xapian::MSetIterator &it) { return Xapian::MSetIterator(it); }
   inline void mset_iterator_decrement(Xapian::MSetIterator &it) { it--; }
   inline Xapian::docid mset_iterator_docid(const Xapian::MSetIterator &it) { return *it; }
@@ -90,37 +123,277 @@ namespace shim {
 
   inline Xapian::PositionIterator position_iterator_copy(const Xapian::PositionIterator &it) { return Xapian::PositionIterator(it); }
   inline bool position_iterator_eq(const Xapian::PositionIterator &a, const Xapian::PositionIterator &b) { return a == b; }
-  inline void position_iterator_increment(Xapian::PositionIterator &it) { it++; }
-  inline Xapian::termpos position_iterator_position(const Xapian::PositionIterator &it) { return *it; }
+
+  inline Xapian::PostingIterator posting_iterator_copy(const Xapian::PostingIterator &it) { return Xapian::PostingIterator(it); }
+  inline bool posting_iterator_eq(const Xapian::PostingIterator &a, const Xapian::PostingIterator &b) { return a == b; }
 
   inline Xapian::Query query_clone(const Xapian::Query &q) { return Xapian::Query(q); }
 
   inline void query_parser_set_stopper(Xapian::QueryParser &qp, const FfiStopper *stopper) { qp.set_stopper(stopper); }
-  inline void query_parser_add_boolean_prefix(
-      Xapian::QueryParser &qp, const std::string &field,
-      FfiFieldProcessor *proc, const std::string *grouping
-  ) { return qp.add_boolean_prefix(field, proc, grouping); }
-  inline void query_parser_add_prefix(Xapian::QueryParser &qp, const std::string &field, FfiFieldProcessor *proc) {
-    return qp.add_prefix(field, proc);
-  }
-  inline void query_parser_add_range_processor(Xapian::QueryParser &qp, FfiRangeProcessor *rp, std::string *grouping) {
+  inline void query_parser_add_range_processor(Xapian::QueryParser &qp, FfiRangeProcessor *rp, const std::string *grouping) {
     return qp.add_rangeprocessor(rp, grouping);
   }
 
   inline Xapian::Query range_processor_evaluate_range(Xapian::RangeProcessor &rp, const std::string &start, const std::string &end) { return rp(start, end); }
 
+  inline Xapian::Stem stem_copy(const Xapian::Stem &stem) { return Xapian::Stem(stem); }
+
   inline std::string stemmer_stem(const Xapian::Stem &stem, const std::string &word) { return stem(word); }
 
   inline void term_generator_set_stopper(Xapian::TermGenerator &tg, const FfiStopper *stopper) { return tg.set_stopper(stopper); }
 
   inline Xapian::TermIterator term_iterator_copy(const Xapian::TermIterator &it) { return Xapian::TermIterator(it); }
   inline bool term_iterator_eq(const Xapian::TermIterator &a, const Xapian::TermIterator &b) { return a == b; }
-  inline void term_iterator_increment(Xapian::TermIterator &it) { it++; }
-  inline std::string term_iterator_term(const Xapian::TermIterator &it) { return *it; }
+
+  inline Xapian::ValueIterator value_iterator_copy(const Xapian::ValueIterator &it) { return Xapian::ValueIterator(it); }
+  inline Xapian::docid value_iterator_docid(const Xapian::ValueIterator &it) { return it.get_docid(); }
+  inline bool value_iterator_eq(const Xapian::ValueIterator &a, const Xapian::ValueIterator &b) { return a == b; }
+  inline Xapian::valueno value_iterator_valueno(const Xapian::ValueIterator &it) { return it.get_valueno(); }
 
   inline int wildcard_limit_behavior_to_int(const WildcardLimitBehavior b) { return b; }
 
+  inline Xapian::WritableDatabase writable_database_copy(const Xapian::WritableDatabase &db) { return Xapian::WritableDatabase(db); }
   inline const Xapian::Database& writable_database_upcast(const Xapian::WritableDatabase &db) { return db; }
 }
 
+// Wrappers around Xapian calls which may throw. These are bound manually via `cxx`, which
+// surfaces exceptions as `Result`s (see `rust::behavior::trycatch` above), rather than via
+// `autocxx`, which does not support exceptions
+namespace fallible {
+  inline std::unique_ptr<Xapian::Database> database_open(const std::string &path, int flags) {
+    return std::make_unique<Xapian::Database>(path, flags);
+  }
+  inline std::unique_ptr<Xapian::Database> database_open_fd(int fd, int flags) {
+    return std::make_unique<Xapian::Database>(fd, flags);
+  }
+  inline void database_add_database(Xapian::Database &db, const Xapian::Database &other) { db.add_database(other); }
+  inline std::unique_ptr<Xapian::TermIterator> database_allterms_begin(const Xapian::Database &db, const std::string &prefix) {
+    return std::make_unique<Xapian::TermIterator>(db.allterms_begin(prefix));
+  }
+  inline std::size_t database_check(const std::string &path, int opts, std::string &output) {
+    std::ostringstream out;
+    try {
+      std::size_t errors = Xapian::Database::check(path, opts, &out);
+      output = out.str();
+      return errors;
+    } catch (...) {
+      // Keep whatever was reported before the failure, as it helps to locate the problem
+      output = out.str();
+      throw;
+    }
+  }
+  inline void database_close(Xapian::Database &db) { db.close(); }
+  inline void database_compact(const Xapian::Database &db, const std::string &output, unsigned flags, int block_size, shim::FfiCompactor *compactor) {
+    if (compactor) {
+      db.compact(output, flags, block_size, *compactor);
+    } else {
+      db.compact(output, flags, block_size);
+    }
+  }
+  inline double database_get_avlength(const Xapian::Database &db) { return db.get_avlength(); }
+  inline Xapian::termcount database_get_collection_freq(const Xapian::Database &db, const std::string &term) {
+    return db.get_collection_freq(term);
+  }
+  inline Xapian::doccount database_get_doccount(const Xapian::Database &db) { return db.get_doccount(); }
+  inline Xapian::termcount database_get_doclength(const Xapian::Database &db, Xapian::docid id) { return db.get_doclength(id); }
+  inline Xapian::termcount database_get_doclength_lower_bound(const Xapian::Database &db) { return db.get_doclength_lower_bound(); }
+  inline Xapian::termcount database_get_doclength_upper_bound(const Xapian::Database &db) { return db.get_doclength_upper_bound(); }
+  inline std::unique_ptr<Xapian::Document> database_get_document(const Xapian::Database &db, Xapian::docid id) {
+    return std::make_unique<Xapian::Document>(db.get_document(id));
+  }
+  inline Xapian::docid database_get_lastdocid(const Xapian::Database &db) { return db.get_lastdocid(); }
+  inline std::unique_ptr<std::string> database_get_metadata(const Xapian::Database &db, const std::string &key) {
+    return std::make_unique<std::string>(db.get_metadata(key));
+  }
+  inline std::uint64_t database_get_revision(const Xapian::Database &db) { return db.get_revision(); }
+  inline std::unique_ptr<std::string> database_get_spelling_suggestion(const Xapian::Database &db, const std::string &word, unsigned max_edit_distance) {
+    return std::make_unique<std::string>(db.get_spelling_suggestion(word, max_edit_distance));
+  }
+  inline Xapian::doccount database_get_termfreq(const Xapian::Database &db, const std::string &term) { return db.get_termfreq(term); }
+  // Xapian::totallength may not be the same type as std::uint64_t, which cxx requires exactly
+  inline std::uint64_t database_get_total_length(const Xapian::Database &db) { return db.get_total_length(); }
+  inline Xapian::termcount database_get_unique_terms(const Xapian::Database &db, Xapian::docid id) { return db.get_unique_terms(id); }
+  inline std::unique_ptr<std::string> database_get_uuid(const Xapian::Database &db) { return std::make_unique<std::string>(db.get_uuid()); }
+  inline Xapian::doccount database_get_value_freq(const Xapian::Database &db, Xapian::valueno slot) { return db.get_value_freq(slot); }
+  inline std::unique_ptr<std::string> database_get_value_lower_bound(const Xapian::Database &db, Xapian::valueno slot) {
+    return std::make_unique<std::string>(db.get_value_lower_bound(slot));
+  }
+  inline std::unique_ptr<std::string> database_get_value_upper_bound(const Xapian::Database &db, Xapian::valueno slot) {
+    return std::make_unique<std::string>(db.get_value_upper_bound(slot));
+  }
+  inline Xapian::termcount database_get_wdf_upper_bound(const Xapian::Database &db, const std::string &term) {
+    return db.get_wdf_upper_bound(term);
+  }
+  inline bool database_has_document(const Xapian::Database &db, Xapian::docid id) {
+    // Every document is indexed by the empty term, so this avoids loading the document itself
+    Xapian::PostingIterator it = db.postlist_begin("");
+    it.skip_to(id);
+    return it != db.postlist_end("") && *it == id;
+  }
+  inline bool database_has_positions(const Xapian::Database &db) { return db.has_positions(); }
+  inline std::unique_ptr<Xapian::TermIterator> database_metadata_keys_begin(const Xapian::Database &db, const std::string &prefix) {
+    return std::make_unique<Xapian::TermIterator>(db.metadata_keys_begin(prefix));
+  }
+  inline std::unique_ptr<Xapian::PostingIterator> database_postlist_begin(const Xapian::Database &db, const std::string &term) {
+    return std::make_unique<Xapian::PostingIterator>(db.postlist_begin(term));
+  }
+  inline bool database_reopen(Xapian::Database &db) { return db.reopen(); }
+  inline std::unique_ptr<Xapian::TermIterator> database_spellings_begin(const Xapian::Database &db) {
+    return std::make_unique<Xapian::TermIterator>(db.spellings_begin());
+  }
+  inline std::unique_ptr<Xapian::TermIterator> database_synonym_keys_begin(const Xapian::Database &db, const std::string &prefix) {
+    return std::make_unique<Xapian::TermIterator>(db.synonym_keys_begin(prefix));
+  }
+  inline std::unique_ptr<Xapian::TermIterator> database_synonyms_begin(const Xapian::Database &db, const std::string &term) {
+    return std::make_unique<Xapian::TermIterator>(db.synonyms_begin(term));
+  }
+  inline bool database_term_exists(const Xapian::Database &db, const std::string &term) { return db.term_exists(term); }
+  inline std::unique_ptr<Xapian::ValueIterator> database_valuestream_begin(const Xapian::Database &db, Xapian::valueno slot) {
+    return std::make_unique<Xapian::ValueIterator>(db.valuestream_begin(slot));
+  }
+
+  inline void document_add_boolean_term(Xapian::Document &doc, const std::string &term) { doc.add_boolean_term(term); }
+  inline void document_add_posting(Xapian::Document &doc, const std::string &term, Xapian::termpos pos, Xapian::termcount increment) {
+    doc.add_posting(term, pos, increment);
+  }
+  inline void document_add_term(Xapian::Document &doc, const std::string &term, Xapian::termcount increment) {
+    doc.add_term(term, increment);
+  }
+  inline void document_add_value(Xapian::Document &doc, Xapian::valueno slot, const std::string &value) { doc.add_value(slot, value); }
+  inline std::unique_ptr<std::string> document_get_data(const Xapian::Document &doc) {
+    return std::make_unique<std::string>(doc.get_data());
+  }
+  inline std::unique_ptr<std::string> document_get_value(const Xapian::Document &doc, Xapian::valueno slot) {
+    return std::make_unique<std::string>(doc.get_value(slot));
+  }
+  inline void document_remove_posting(Xapian::Document &doc, const std::string &term, Xapian::termpos pos, Xapian::termcount decrement) {
+    doc.remove_posting(term, pos, decrement);
+  }
+  inline Xapian::termpos document_remove_postings(
+      Xapian::Document &doc, const std::string &term, Xapian::termpos start, Xapian::termpos end, Xapian::termcount decrement) {
+    return doc.remove_postings(term, start, end, decrement);
+  }
+  inline void document_remove_term(Xapian::Document &doc, const std::string &term) { doc.remove_term(term); }
+  inline void document_remove_value(Xapian::Document &doc, Xapian::valueno slot) { doc.remove_value(slot); }
+  inline std::unique_ptr<std::string> document_serialise(const Xapian::Document &doc) {
+    return std::make_unique<std::string>(doc.serialise());
+  }
+  inline std::unique_ptr<Xapian::TermIterator> document_termlist_begin(const Xapian::Document &doc) {
+    return std::make_unique<Xapian::TermIterator>(doc.termlist_begin());
+  }
+  inline Xapian::termcount document_termlist_count(const Xapian::Document &doc) { return doc.termlist_count(); }
+  inline std::unique_ptr<Xapian::Document> document_unserialise(const std::string &data) {
+    return std::make_unique<Xapian::Document>(Xapian::Document::unserialise(data));
+  }
+  inline std::unique_ptr<Xapian::ValueIterator> document_values_begin(const Xapian::Document &doc) {
+    return std::make_unique<Xapian::ValueIterator>(doc.values_begin());
+  }
+  inline Xapian::termcount document_values_count(const Xapian::Document &doc) { return doc.values_count(); }
+
+  inline std::unique_ptr<Xapian::ESet> enquire_get_eset(
+      const Xapian::Enquire &e, Xapian::termcount maxitems, const Xapian::RSet &rset,
+      int flags, const shim::FfiExpandDecider *decider, double min_wt
+  ) { return std::make_unique<Xapian::ESet>(e.get_eset(maxitems, rset, flags, decider, min_wt)); }
+  inline std::unique_ptr<Xapian::MSet> enquire_get_mset(
+      const Xapian::Enquire &e, Xapian::doccount first,
+      Xapian::doccount maxitems,  Xapian::doccount atleast,
+      const Xapian::RSet *rset, const shim::FfiMatchDecider *decider
+  ) { return std::make_unique<Xapian::MSet>(e.get_mset(first, maxitems, atleast, rset, decider)); }
+
+  inline Xapian::doccount mset_get_termfreq(const Xapian::MSet &mset, const std::string &term) { return mset.get_termfreq(term); }
+  inline std::unique_ptr<std::string> mset_snippet(
+      const Xapian::MSet &mset, const std::string &text, std::size_t length, const Xapian::Stem &stemmer,
+      unsigned flags, const std::string &hl_start, const std::string &hl_end, const std::string &omit
+  ) { return std::make_unique<std::string>(mset.snippet(text, length, stemmer, flags, hl_start, hl_end, omit)); }
+
+  inline std::unique_ptr<Xapian::Document> mset_iterator_get_document(const Xapian::MSetIterator &it) {
+    return std::make_unique<Xapian::Document>(it.get_document());
+  }
+
+  inline void position_iterator_increment(Xapian::PositionIterator &it) { it++; }
+  inline Xapian::termpos position_iterator_position(const Xapian::PositionIterator &it) { return *it; }
+
+  inline Xapian::docid posting_iterator_docid(const Xapian::PostingIterator &it) { return *it; }
+  inline Xapian::termcount posting_iterator_get_doclength(const Xapian::PostingIterator &it) { return it.get_doclength(); }
+  inline Xapian::termcount posting_iterator_get_unique_terms(const Xapian::PostingIterator &it) { return it.get_unique_terms(); }
+  inline Xapian::termcount posting_iterator_get_wdf(const Xapian::PostingIterator &it) { return it.get_wdf(); }
+  inline std::unique_ptr<Xapian::PositionIterator> posting_iterator_positionlist_begin(const Xapian::PostingIterator &it) {
+    return std::make_unique<Xapian::PositionIterator>(it.positionlist_begin());
+  }
+  inline Xapian::termcount posting_iterator_positionlist_count(const Xapian::PostingIterator &it) { return it.positionlist_count(); }
+  inline void posting_iterator_increment(Xapian::PostingIterator &it) { it++; }
+  inline void posting_iterator_skip_to(Xapian::PostingIterator &it, Xapian::docid id) { it.skip_to(id); }
+
+  inline void query_parser_add_boolean_prefix(
+      Xapian::QueryParser &qp, const std::string &field,
+      const std::string &prefix, const std::string *grouping
+  ) { qp.add_boolean_prefix(field, prefix, grouping); }
+  inline void query_parser_add_custom_boolean_prefix(
+      Xapian::QueryParser &qp, const std::string &field,
+      shim::FfiFieldProcessor *proc, const std::string *grouping
+  ) { qp.add_boolean_prefix(field, proc, grouping); }
+  inline void query_parser_add_custom_prefix(Xapian::QueryParser &qp, const std::string &field, shim::FfiFieldProcessor *proc) {
+    qp.add_prefix(field, proc);
+  }
+  inline void query_parser_add_prefix(Xapian::QueryParser &qp, const std::string &field, const std::string &prefix) {
+    qp.add_prefix(field, prefix);
+  }
+  inline std::unique_ptr<Xapian::Query> query_parser_parse_query(
+      Xapian::QueryParser &qp, const std::string &query, unsigned flags, const std::string &default_prefix
+  ) { return std::make_unique<Xapian::Query>(qp.parse_query(query, flags, default_prefix)); }
+
+  inline std::unique_ptr<Xapian::Stem> stem_for_language(const std::string &lang) { return std::make_unique<Xapian::Stem>(lang); }
+
+  inline void term_generator_index_text(Xapian::TermGenerator &tg, const std::string &text, Xapian::termcount increment, const std::string &prefix) {
+    tg.index_text(text, increment, prefix);
+  }
+
+  inline Xapian::termcount term_iterator_get_termfreq(const Xapian::TermIterator &it) { return it.get_termfreq(); }
+  inline Xapian::termcount term_iterator_get_wdf(const Xapian::TermIterator &it) { return it.get_wdf(); }
+  inline std::unique_ptr<Xapian::PositionIterator> term_iterator_positionlist_begin(const Xapian::TermIterator &it) {
+    return std::make_unique<Xapian::PositionIterator>(it.positionlist_begin());
+  }
+  inline void term_iterator_increment(Xapian::TermIterator &it) { it++; }
+  inline Xapian::termcount term_iterator_positionlist_count(const Xapian::TermIterator &it) { return it.positionlist_count(); }
+  inline void term_iterator_skip_to(Xapian::TermIterator &it, const std::string &term) { it.skip_to(term); }
+  inline std::unique_ptr<std::string> term_iterator_term(const Xapian::TermIterator &it) { return std::make_unique<std::string>(*it); }
+
+  inline void value_iterator_increment(Xapian::ValueIterator &it) { it++; }
+  inline void value_iterator_skip_to(Xapian::ValueIterator &it, Xapian::docid id) { it.skip_to(id); }
+  inline std::unique_ptr<std::string> value_iterator_value(const Xapian::ValueIterator &it) { return std::make_unique<std::string>(*it); }
+
+  inline std::unique_ptr<Xapian::WritableDatabase> writable_database_open(const std::string &path, int flags, int block_size) {
+    return std::make_unique<Xapian::WritableDatabase>(path, flags, block_size);
+  }
+  inline void writable_database_add_database(Xapian::WritableDatabase &db, const Xapian::WritableDatabase &other) { db.add_database(other); }
+  inline Xapian::docid writable_database_add_document(Xapian::WritableDatabase &db, const Xapian::Document &doc) { return db.add_document(doc); }
+  inline void writable_database_add_spelling(const Xapian::WritableDatabase &db, const std::string &word, Xapian::termcount increment) {
+    db.add_spelling(word, increment);
+  }
+  inline void writable_database_add_synonym(const Xapian::WritableDatabase &db, const std::string &term, const std::string &synonym) {
+    db.add_synonym(term, synonym);
+  }
+  inline void writable_database_begin_transaction(Xapian::WritableDatabase &db, bool flushed) { db.begin_transaction(flushed); }
+  inline void writable_database_cancel_transaction(Xapian::WritableDatabase &db) { db.cancel_transaction(); }
+  inline void writable_database_commit(Xapian::WritableDatabase &db) { db.commit(); }
+  inline void writable_database_commit_transaction(Xapian::WritableDatabase &db) { db.commit_transaction(); }
+  inline void writable_database_delete_document(Xapian::WritableDatabase &db, Xapian::docid id) { db.delete_document(id); }
+  inline void writable_database_delete_document_by_term(Xapian::WritableDatabase &db, const std::string &term) { db.delete_document(term); }
+  inline void writable_database_remove_spelling(const Xapian::WritableDatabase &db, const std::string &word, Xapian::termcount decrement) {
+    db.remove_spelling(word, decrement);
+  }
+  inline void writable_database_remove_synonym(const Xapian::WritableDatabase &db, const std::string &term, const std::string &synonym) {
+    db.remove_synonym(term, synonym);
+  }
+  inline void writable_database_replace_document(Xapian::WritableDatabase &db, Xapian::docid id, const Xapian::Document &doc) {
+    db.replace_document(id, doc);
+  }
+  inline Xapian::docid writable_database_replace_document_by_term(Xapian::WritableDatabase &db, const std::string &term, const Xapian::Document &doc) {
+    return db.replace_document(term, doc);
+  }
+  inline void writable_database_set_metadata(Xapian::WritableDatabase &db, const std::string &key, const std::string &value) {
+    db.set_metadata(key, value);
+  }
+}
+
 #endif
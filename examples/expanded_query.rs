@@ -29,31 +29,31 @@ impl ExpandDecider for DescriptionDecider<'_> {
 }
 
 fn main() -> anyhow::Result<()> {
-    let stemmer = Stem::for_language("english");
+    let stemmer = Stem::for_language("english")?;
 
     let args = Args::parse();
-    let db = Database::open(args.db.join("museum"), None);
+    let db = Database::open(args.db.join("museum"), None)?;
     let qstr = args.queries.join(" ");
 
     let mut qp = QueryParser::default();
-    qp.add_prefix("description", "XD:");
+    qp.add_prefix("description", "XD:")?;
 
     let decider = DescriptionDecider::from_iter(STOPWORDS.lines());
 
     qp.set_stemmer(stemmer);
-    let query = qp.parse_query(qstr, None, "S:");
+    let query = qp.parse_query(qstr, None, "S:")?;
 
     let mut enquire = Enquire::new(db);
     enquire.set_query(&query, None);
-    let mset = enquire.mset(0, 100, 100, None);
+    let mset = enquire.mset(0, 100, 100, None)?;
     let matches = mset.matches();
     let rset = RSet::from_iter(matches.clone().take(2));
     for m in matches {
-        println!("{}", m.document());
+        println!("{}", m.document()?);
     }
 
     println!("Consider adding:");
-    for term in enquire.eset(100, rset, 0, decider, 0.0).terms() {
+    for term in enquire.eset(100, rset, 0, decider, 0.0)?.terms() {
         println!("\t{term}")
     }
 
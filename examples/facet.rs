@@ -29,7 +29,7 @@ impl<T: FromValue> ValueSpy<T> {
 
 impl<T: FromValue + Ord> MatchSpy for ValueSpy<T> {
     fn observe(&self, doc: &xapian_rs::Document, _: f64) {
-        if let Some(Ok(key)) = doc.value::<T>(self.slot) {
+        if let Ok(Some(Ok(key))) = doc.value::<T>(self.slot) {
             let mut stats = self.stats.borrow_mut();
             let count = stats.entry(key).or_insert(0);
             *count += 1;
@@ -56,7 +56,7 @@ impl<T: FromValue + Ord, F: Fn(&T) -> T> BucketingValueSpy<T, F> {
 
 impl<T: FromValue + Ord, F: Fn(&T) -> T> MatchSpy for BucketingValueSpy<T, F> {
     fn observe(&self, doc: &xapian_rs::Document, _: f64) {
-        if let Some(Ok(key)) = doc.value::<T>(self.slot) {
+        if let Ok(Some(Ok(key))) = doc.value::<T>(self.slot) {
             let bucket_key = (self.bucket_fn)(&key);
             let mut stats = self.stats.borrow_mut();
             let count = stats.entry(bucket_key).or_insert(0);
@@ -66,28 +66,29 @@ impl<T: FromValue + Ord, F: Fn(&T) -> T> MatchSpy for BucketingValueSpy<T, F> {
 }
 
 fn main() -> anyhow::Result<()> {
-    let stemmer = Stem::for_language("english");
+    let stemmer = Stem::for_language("english")?;
 
     let args = Args::parse();
-    let db = Database::open(args.db.join("museum"), None);
+    let db = Database::open(args.db.join("museum"), None)?;
     let qstr = args.queries.join(" ");
 
     let spy = ValueSpy::<u32>::new(1);
     let bucket_spy = BucketingValueSpy::<u32, _>::new(1, |x| (x / 100) * 100);
     let mut qp = QueryParser::default();
-    qp.add_prefix("description", "XD:");
+    qp.add_prefix("description", "XD:")?;
 
     qp.set_stemmer(stemmer);
-    let query = qp.parse_query(qstr, None, "S:");
+    let query = qp.parse_query(qstr, None, "S:")?;
     eprintln!("query:{query}");
 
     for term in qp.stoplist() {
-        eprintln!("stopword:{term}");
+        eprintln!("stopword:{}", term?);
     }
 
     for term in query.terms() {
+        let term = term?;
         for t in qp.unstem(&term) {
-            eprintln!("unstem:{term}={t}");
+            eprintln!("unstem:{term}={}", t?);
         }
     }
 
@@ -95,7 +96,7 @@ fn main() -> anyhow::Result<()> {
     enquire.set_query(&query, None);
     enquire.add_matchspy(&spy);
     enquire.add_matchspy(&bucket_spy);
-    let results = enquire.mset(0, 100, 100, None);
+    let results = enquire.mset(0, 100, 100, None)?;
     for (year, count) in spy.stats.borrow().iter() {
         eprintln!("spy:{year}={count}")
     }
@@ -105,7 +106,7 @@ fn main() -> anyhow::Result<()> {
     }
 
     for m in results.matches() {
-        println!("{}", m.document());
+        println!("{}", m.document()?);
     }
 
     Ok(())
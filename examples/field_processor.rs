@@ -23,34 +23,35 @@ impl FieldProcessor for UpperCaseField {
 }
 
 fn main() -> anyhow::Result<()> {
-    let stemmer = Stem::for_language("english");
+    let stemmer = Stem::for_language("english")?;
 
     let args = Args::parse();
-    let db = Database::open(args.db.join("museum"), None);
+    let db = Database::open(args.db.join("museum"), None)?;
     let qstr = args.queries.join(" ");
     eprintln!("qstr={qstr}");
 
     let mut qp = QueryParser::default();
-    qp.add_custom_prefix("description", UpperCaseField);
+    qp.add_custom_prefix("description", UpperCaseField)?;
 
     qp.set_stemmer(stemmer);
-    let query = qp.parse_query(qstr, None, "");
+    let query = qp.parse_query(qstr, None, "")?;
     eprintln!("query:{query}");
 
     for term in qp.stoplist() {
-        eprintln!("stopword:{term}");
+        eprintln!("stopword:{}", term?);
     }
 
     for term in query.terms() {
+        let term = term?;
         for t in qp.unstem(&term) {
-            eprintln!("unstem:{term}={t}");
+            eprintln!("unstem:{term}={}", t?);
         }
     }
 
     let mut enquire = Enquire::new(db);
     enquire.set_query(&query, None);
-    for m in enquire.mset(0, 100, 100, None).matches() {
-        println!("{}", m.document());
+    for m in enquire.mset(0, 100, 100, None)?.matches() {
+        println!("{}", m.document()?);
     }
 
     Ok(())
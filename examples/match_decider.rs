@@ -37,42 +37,43 @@ where
 {
     fn is_match(&self, doc: &xapian_rs::Document) -> bool {
         let value = doc.value::<T>(self.slot);
-        matches!(value, Some(Ok(t)) if t != self.value)
+        matches!(value, Ok(Some(Ok(t))) if t != self.value)
     }
 }
 
 fn main() -> anyhow::Result<()> {
-    let stemmer = Stem::for_language("english");
+    let stemmer = Stem::for_language("english")?;
 
     let args = Args::parse();
-    let db = Database::open(args.db.join("museum"), None);
+    let db = Database::open(args.db.join("museum"), None)?;
     let qstr = args.queries.join(" ");
     let decider = RejectDecider::new(1, args.reject);
 
     let mut qp = QueryParser::default();
-    qp.add_prefix("description", "XD:");
+    qp.add_prefix("description", "XD:")?;
 
     qp.set_stemmer(stemmer);
-    let query = qp.parse_query(qstr, None, "S:");
+    let query = qp.parse_query(qstr, None, "S:")?;
     eprintln!("query:{query}");
 
     for term in qp.stoplist() {
-        eprintln!("stopword:{term}");
+        eprintln!("stopword:{}", term?);
     }
 
     for term in query.terms() {
+        let term = term?;
         for t in qp.unstem(&term) {
-            eprintln!("unstem:{term}={t}");
+            eprintln!("unstem:{term}={}", t?);
         }
     }
 
     let mut enquire = Enquire::new(db);
     enquire.set_query(&query, None);
     for m in enquire
-        .mset_with_decider(0, 100, 100, None, decider)
+        .mset_with_decider(0, 100, 100, None, decider)?
         .matches()
     {
-        println!("{}", m.document());
+        println!("{}", m.document()?);
     }
 
     Ok(())
@@ -27,37 +27,38 @@ impl Stopper for MyStopper {
 }
 
 fn main() -> anyhow::Result<()> {
-    let stemmer = Stem::for_language("english");
+    let stemmer = Stem::for_language("english")?;
 
     let stopwords = ["a", "an", "the"];
     let stopper = MyStopper::from_iter(&stopwords);
 
     let args = Args::parse();
-    let db = Database::open(args.db.join("museum"), None);
+    let db = Database::open(args.db.join("museum"), None)?;
     let qstr = args.queries.join(" ");
 
     let mut qp = QueryParser::default();
-    qp.add_prefix("description", "XD:");
+    qp.add_prefix("description", "XD:")?;
 
     qp.set_stemmer(stemmer);
     qp.set_stopper(stopper);
-    let query = qp.parse_query(qstr, None, "S:");
+    let query = qp.parse_query(qstr, None, "S:")?;
     eprintln!("query:{query}");
 
     for term in qp.stoplist() {
-        eprintln!("stopword:{term}");
+        eprintln!("stopword:{}", term?);
     }
 
     for term in query.terms() {
+        let term = term?;
         for t in qp.unstem(&term) {
-            eprintln!("unstem:{term}={t}");
+            eprintln!("unstem:{term}={}", t?);
         }
     }
 
     let mut enquire = Enquire::new(db);
     enquire.set_query(&query, None);
-    for m in enquire.mset(0, 100, 100, None).matches() {
-        println!("{}", m.document());
+    for m in enquire.mset(0, 100, 100, None)?.matches() {
+        println!("{}", m.document()?);
     }
 
     Ok(())
@@ -0,0 +1,212 @@
+use std::time::{Duration, Instant};
+
+/// Statistics describing the work done by a [`BulkIndexer`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BulkStats {
+    added: u64,
+    replaced: u64,
+    deleted: u64,
+    commits: u64,
+    bytes: u64,
+    elapsed: Duration,
+}
+
+impl BulkStats {
+    /// The number of documents which were added
+    pub fn added(&self) -> u64 {
+        self.added
+    }
+
+    /// The number of existing documents which were replaced
+    pub fn replaced(&self) -> u64 {
+        self.replaced
+    }
+
+    /// The number of documents which were deleted
+    pub fn deleted(&self) -> u64 {
+        self.deleted
+    }
+
+    /// The number of commits made
+    pub fn commits(&self) -> u64 {
+        self.commits
+    }
+
+    /// The estimated total size (in bytes) of the documents which were added or replaced
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The time spent indexing so far
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The number of documents added or replaced per second
+    pub fn docs_per_sec(&self) -> f64 {
+        per_sec((self.added + self.replaced) as f64, self.elapsed)
+    }
+
+    /// The number of bytes added or replaced per second
+    pub fn bytes_per_sec(&self) -> f64 {
+        per_sec(self.bytes as f64, self.elapsed)
+    }
+}
+
+fn per_sec(amount: f64, elapsed: Duration) -> f64 {
+    match elapsed.as_secs_f64() {
+        secs if secs > 0.0 => amount / secs,
+        _ => 0.0,
+    }
+}
+
+/// Adds, replaces and deletes documents in a [`WritableDatabase`][crate::WritableDatabase] in
+/// batches, committing whenever a batch grows too large
+///
+/// The batch limits only control when `BulkIndexer` commits. Xapian also commits by itself once a
+/// database has 10000 uncommitted changes, so it may still commit part of a larger batch. Raise
+/// that threshold by setting `XAPIAN_FLUSH_THRESHOLD` before spawning any threads, or with
+/// [`OpenOptions::flush_threshold`][crate::OpenOptions::flush_threshold] when opening the
+/// database.
+///
+/// Call [`BulkIndexer::finish`] to commit the final batch. Changes which are still pending when a
+/// `BulkIndexer` is dropped are left uncommitted in the database
+pub struct BulkIndexer<'db> {
+    db: &'db mut crate::WritableDatabase,
+    commit_interval: u32,
+    max_batch_bytes: Option<u64>,
+    pending_docs: u32,
+    pending_bytes: u64,
+    started: Instant,
+    stats: BulkStats,
+}
+
+impl<'db> BulkIndexer<'db> {
+    /// Create a `BulkIndexer` which commits to `db` every 10000 changes
+    pub fn new(db: &'db mut crate::WritableDatabase) -> Self {
+        Self {
+            db,
+            commit_interval: 10000,
+            max_batch_bytes: None,
+            pending_docs: 0,
+            pending_bytes: 0,
+            started: Instant::now(),
+            stats: BulkStats::default(),
+        }
+    }
+
+    /// Commit after every `changes` documents are added, replaced or deleted
+    pub fn commit_interval(mut self, changes: u32) -> Self {
+        self.commit_interval = changes.max(1);
+        self
+    }
+
+    /// Also commit once the estimated size of the documents added or replaced since the last
+    /// commit exceeds `bytes`
+    ///
+    /// Sizes are estimated from each document's data and its number of terms and values, rather
+    /// than by serialising it
+    pub fn max_batch_bytes(mut self, bytes: impl Into<Option<u64>>) -> Self {
+        self.max_batch_bytes = bytes.into();
+        self
+    }
+
+    /// Add a new document to the database
+    pub fn add(&mut self, doc: &crate::Document) -> crate::Result<crate::DocId> {
+        let size = doc.estimated_len()? as u64;
+        let id = self.db.add_document(doc)?;
+        self.stats.added += 1;
+        self.changed(size)?;
+        Ok(id)
+    }
+
+    /// Replace the document indexed by `unique_term`, or add `doc` if there is no such document
+    ///
+    /// If several documents are indexed by `unique_term`, the first is replaced and the rest are
+    /// deleted
+    pub fn upsert(
+        &mut self,
+        unique_term: impl AsRef<[u8]>,
+        doc: &crate::Document,
+    ) -> crate::Result<crate::DocId> {
+        let size = doc.estimated_len()? as u64;
+        let before = self.db.doc_count()?;
+        let id = self.db.replace_document_by_term(unique_term, doc)?;
+
+        // Every document indexed by the term is replaced by a single one, so the change in the
+        // document count shows how many there were, without looking the term up
+        match before + 1 - self.db.doc_count()? {
+            0 => self.stats.added += 1,
+            matched => {
+                self.stats.replaced += 1;
+                self.stats.deleted += u64::from(matched - 1);
+            }
+        }
+        self.changed(size)?;
+        Ok(unsafe { crate::DocId::new_unchecked(id) })
+    }
+
+    /// Delete the document (if any) matching the specified [`DocId`][crate::DocId]
+    ///
+    /// Returns `true` if a document was deleted
+    pub fn delete(&mut self, id: impl Into<crate::DocId>) -> crate::Result<bool> {
+        let id: crate::DocId = id.into();
+        if !self.db.has_document(id)? {
+            return Ok(false);
+        }
+        self.db.delete_document(id)?;
+        self.stats.deleted += 1;
+        self.changed(0)?;
+        Ok(true)
+    }
+
+    /// Delete every document indexed by `term`, returning the number of documents deleted
+    pub fn delete_by_term(&mut self, term: impl AsRef<[u8]>) -> crate::Result<u32> {
+        let count = self.db.termfreq(term.as_ref())?;
+        if count > 0 {
+            self.db.delete_document_by_term(term)?;
+            self.stats.deleted += u64::from(count);
+            self.changed(0)?;
+        }
+        Ok(count)
+    }
+
+    /// Commit the current batch now, if it has any pending changes
+    pub fn flush(&mut self) -> crate::Result<()> {
+        if self.pending_docs > 0 {
+            self.db.commit()?;
+            self.stats.commits += 1;
+            self.pending_docs = 0;
+            self.pending_bytes = 0;
+        }
+        Ok(())
+    }
+
+    /// Get the statistics gathered so far
+    pub fn stats(&self) -> BulkStats {
+        BulkStats {
+            elapsed: self.started.elapsed(),
+            ..self.stats
+        }
+    }
+
+    /// Commit the final batch, returning a summary of the work done
+    pub fn finish(mut self) -> crate::Result<BulkStats> {
+        self.flush()?;
+        Ok(self.stats())
+    }
+
+    fn changed(&mut self, bytes: u64) -> crate::Result<()> {
+        self.pending_docs += 1;
+        self.pending_bytes += bytes;
+        self.stats.bytes += bytes;
+
+        let too_large = self
+            .max_batch_bytes
+            .is_some_and(|max| self.pending_bytes >= max);
+        if self.pending_docs >= self.commit_interval || too_large {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
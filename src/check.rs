@@ -0,0 +1,146 @@
+use crate::ffi::{fallible, ToCxxString};
+
+use std::{io::Write, path::Path};
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// Flags controlling what [`Database::check`][crate::Database::check] checks and reports
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct CheckFlags: u32 {
+        /// Show a short-format display of the B-tree contents
+        const SHORT_TREE = 0x01;
+        /// Show a full display of the B-tree contents
+        const FULL_TREE = 0x02;
+        /// Show the bitmap of free blocks in each B-tree
+        const SHOW_FREELIST = 0x04;
+        /// Show statistics for each B-tree
+        const SHOW_STATS = 0x08;
+        /// Fix problems where possible, such as an incorrect document length upper bound
+        const FIX = 0x10;
+    }
+}
+
+/// The errors found in a single table by a [`Database::check`][crate::Database::check]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableReport {
+    name: String,
+    errors: Vec<String>,
+}
+
+impl TableReport {
+    /// A description of each error found in this table
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Returns `true` if no errors were found in this table
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The name of the table, such as `postlist`
+    ///
+    /// Problems which don't belong to a table, such as a damaged version file, are reported
+    /// under an empty name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The outcome of a [`Database::check`][crate::Database::check]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckReport {
+    errors: usize,
+    tables: Vec<TableReport>,
+}
+
+impl CheckReport {
+    /// The number of errors which were found, as counted by Xapian
+    pub fn errors(&self) -> usize {
+        self.errors
+    }
+
+    /// Returns an iterator over the tables in which errors were found
+    pub fn failed_tables(&self) -> impl Iterator<Item = &TableReport> {
+        self.tables.iter().filter(|t| !t.is_ok())
+    }
+
+    /// Returns `true` if no errors were found
+    pub fn is_ok(&self) -> bool {
+        self.errors == 0
+    }
+
+    /// The tables which were checked, in the order they were checked
+    pub fn tables(&self) -> &[TableReport] {
+        &self.tables
+    }
+
+    // Without any display flags, Xapian writes a `<table>:` header before checking each table,
+    // followed by a line for each error and a few fixed progress messages
+    fn parse(errors: usize, output: &str) -> Self {
+        let mut tables: Vec<TableReport> = Vec::new();
+        for line in output.lines().map(str::trim) {
+            let header = line
+                .strip_suffix(':')
+                .filter(|name| !name.is_empty() && name.bytes().all(|b| b.is_ascii_lowercase()));
+            if let Some(name) = header {
+                tables.push(TableReport {
+                    name: name.to_string(),
+                    errors: Vec::new(),
+                });
+                continue;
+            }
+
+            let progress = line.is_empty()
+                || line.to_ascii_lowercase().contains("checked okay")
+                || line.starts_with("Lazily created");
+            if progress {
+                continue;
+            }
+
+            if tables.is_empty() {
+                tables.push(TableReport {
+                    name: String::new(),
+                    errors: Vec::new(),
+                });
+            }
+            tables.last_mut().unwrap().errors.push(line.to_string());
+        }
+        Self { errors, tables }
+    }
+}
+
+fn run_check(path: &Path, flags: CheckFlags) -> (crate::Result<usize>, Vec<u8>) {
+    cxx::let_cxx_string!(output = "");
+    let result =
+        fallible::database_check(&path.to_cxx_string(), flags.bits() as i32, output.as_mut());
+    (result.map_err(Into::into), output.as_bytes().to_vec())
+}
+
+pub(crate) fn check(
+    path: &Path,
+    flags: CheckFlags,
+    mut out: impl Write,
+) -> crate::Result<CheckReport> {
+    let display = CheckFlags::SHORT_TREE
+        | CheckFlags::FULL_TREE
+        | CheckFlags::SHOW_FREELIST
+        | CheckFlags::SHOW_STATS;
+
+    // The report is parsed from a check without display flags, whose output is only headers and
+    // errors. If any were requested, the tables are checked again to write the full output
+    let (result, output) = run_check(path, flags - display);
+    if result.is_ok() && flags.intersects(display) {
+        out.write_all(&run_check(path, flags & display).1)?;
+    } else {
+        // Output is written even if the check failed part way, as it may help explain why
+        out.write_all(&output)?;
+    }
+    out.flush()?;
+
+    Ok(CheckReport::parse(
+        result?,
+        &String::from_utf8_lossy(&output),
+    ))
+}
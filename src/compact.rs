@@ -0,0 +1,162 @@
+use crate::ffi::{self, fallible, ToCxxString};
+
+use std::{cell::RefCell, path::Path, rc::Rc};
+
+use bytes::Bytes;
+
+/// How thoroughly a database should be compacted
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompactionLevel {
+    /// Don't split items unnecessarily
+    #[default]
+    Standard = 0,
+    /// Split items whenever it saves space
+    Full = 1,
+    /// Allow oversized blocks, in order to save even more space
+    Fuller = 2,
+}
+
+const DBCOMPACT_NO_RENUMBER: u32 = 4;
+const DBCOMPACT_MULTIPASS: u32 = 8;
+const DBCOMPACT_SINGLE_FILE: u32 = 16;
+
+type ProgressFn = Box<dyn FnMut(&str, &str) + 'static>;
+type ResolveFn = Box<dyn FnMut(&str, &[Bytes]) -> Bytes + 'static>;
+
+/// Options controlling how a database is compacted by [`Database::compact`][crate::Database::compact]
+///
+/// Compacting a [`Database`][crate::Database] made up of several shards (see
+/// [`Database::from_shards`][crate::Database::from_shards]) merges them into a single database
+pub struct CompactOptions {
+    level: CompactionLevel,
+    block_size: Option<u32>,
+    multipass: bool,
+    renumber: bool,
+    single_file: bool,
+    progress: Option<ProgressFn>,
+    resolve: Option<ResolveFn>,
+}
+
+impl CompactOptions {
+    /// Create a new set of options, which perform a standard compaction
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the block size (in bytes) to use for the compacted database
+    ///
+    /// Must be a power of 2 between 2048 and 65536 (the default is 8192)
+    pub fn block_size(mut self, size: impl Into<Option<u32>>) -> Self {
+        self.block_size = size.into();
+        self
+    }
+
+    /// Set the [`CompactionLevel`] to use
+    pub fn level(mut self, level: CompactionLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Merge the postlists in multiple passes, which is generally faster when merging many shards
+    pub fn multipass(mut self, enabled: bool) -> Self {
+        self.multipass = enabled;
+        self
+    }
+
+    /// Register a closure to be called with progress updates during compaction
+    ///
+    /// The closure receives the name of the table being compacted and a status message
+    pub fn on_progress(mut self, f: impl FnMut(&str, &str) + 'static) -> Self {
+        self.progress = Some(Box::new(f));
+        self
+    }
+
+    /// Register a closure to resolve metadata keys which appear in more than one source shard
+    ///
+    /// The closure receives the metadata key and each of its values, and returns the value to
+    /// keep. If no closure is registered, the value from the first shard is kept
+    pub fn on_duplicate_metadata(
+        mut self,
+        f: impl FnMut(&str, &[Bytes]) -> Bytes + 'static,
+    ) -> Self {
+        self.resolve = Some(Box::new(f));
+        self
+    }
+
+    /// Renumber documents when merging shards, so that their [`DocId`][crate::DocId]s are
+    /// contiguous (enabled by default)
+    ///
+    /// When disabled, the ranges of `DocId`s used by each shard must not overlap
+    pub fn renumber(mut self, enabled: bool) -> Self {
+        self.renumber = enabled;
+        self
+    }
+
+    /// Produce a single-file database, rather than a directory
+    pub fn single_file(mut self, enabled: bool) -> Self {
+        self.single_file = enabled;
+        self
+    }
+
+    fn flags(&self) -> u32 {
+        let mut flags = self.level as u32;
+        if !self.renumber {
+            flags |= DBCOMPACT_NO_RENUMBER;
+        }
+        if self.multipass {
+            flags |= DBCOMPACT_MULTIPASS;
+        }
+        if self.single_file {
+            flags |= DBCOMPACT_SINGLE_FILE;
+        }
+        flags
+    }
+}
+
+impl Default for CompactOptions {
+    fn default() -> Self {
+        Self {
+            level: CompactionLevel::default(),
+            block_size: None,
+            multipass: false,
+            renumber: true,
+            single_file: false,
+            progress: None,
+            resolve: None,
+        }
+    }
+}
+
+struct CompactorObj(Rc<RefCell<ffi::RustCompactor>>);
+
+impl CompactorObj {
+    fn upcast(&mut self) -> *mut ffi::shim::FfiCompactor {
+        use ffi::shim::FfiCompactor_methods;
+        self.0.borrow_mut().upcast()
+    }
+}
+
+pub(crate) fn compact(
+    db: &ffi::Database,
+    dest: &Path,
+    options: CompactOptions,
+) -> crate::Result<()> {
+    let flags = options.flags();
+    let block_size = options.block_size.unwrap_or(0) as i32;
+
+    // The compactor is only borrowed by Xapian, so it must outlive the call to compact
+    let mut compactor = (options.progress.is_some() || options.resolve.is_some()).then(|| {
+        CompactorObj(ffi::RustCompactor::from_closures(
+            options.progress,
+            options.resolve,
+        ))
+    });
+    let compactor_ptr = compactor
+        .as_mut()
+        .map_or(std::ptr::null_mut(), |c| c.upcast());
+
+    Ok(unsafe {
+        fallible::database_compact(db, &dest.to_cxx_string(), flags, block_size, compactor_ptr)
+    }?)
+}
@@ -0,0 +1,227 @@
+use crate::{FromValue, ToValue};
+
+use std::fmt::{self, Display};
+
+use bytes::Bytes;
+
+/// A wrapper which stores a date or timestamp as the number of seconds since the Unix epoch,
+/// serialised via [`ToValue`] for `f64`
+///
+/// Without this wrapper, dates are stored as `YYYYMMDD` strings and timestamps as UTC
+/// `YYYYMMDDHHMMSS` strings, which sort correctly and are readable, but drop fractional seconds.
+/// Epoch values keep fractional seconds (to the precision of an `f64`), and can be compared with
+/// other numeric values
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Epoch<T>(pub T);
+
+/// The error returned when a value holds a timestamp which is out of range for the requested type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampOutOfRange;
+
+impl Display for TimestampOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timestamp out of range")
+    }
+}
+
+impl std::error::Error for TimestampOutOfRange {}
+
+fn serialize_epoch(secs: i64, nanos: u32) -> Bytes {
+    (secs as f64 + f64::from(nanos) / 1e9).serialize()
+}
+
+fn deserialize_epoch(value: Bytes) -> (i64, u32) {
+    let secs = match f64::deserialize(value) {
+        Ok(secs) => secs,
+        Err(e) => match e {},
+    };
+    let whole = secs.floor();
+    let nanos = ((secs - whole) * 1e9).round().min(999_999_999.0) as u32;
+    (whole as i64, nanos)
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_values {
+    use super::{deserialize_epoch, serialize_epoch, Epoch, TimestampOutOfRange};
+    use crate::{FromValue, ToValue};
+
+    use bytes::Bytes;
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+    const DATE: &str = "%Y%m%d";
+    const DATE_TIME: &str = "%Y%m%d%H%M%S";
+
+    impl ToValue for NaiveDate {
+        fn serialize(&self) -> Bytes {
+            self.format(DATE).to_string().into()
+        }
+    }
+
+    impl FromValue for NaiveDate {
+        type Error = chrono::ParseError;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            NaiveDate::parse_from_str(&String::from_utf8_lossy(&value), DATE)
+        }
+    }
+
+    impl ToValue for NaiveDateTime {
+        fn serialize(&self) -> Bytes {
+            self.format(DATE_TIME).to_string().into()
+        }
+    }
+
+    impl FromValue for NaiveDateTime {
+        type Error = chrono::ParseError;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            NaiveDateTime::parse_from_str(&String::from_utf8_lossy(&value), DATE_TIME)
+        }
+    }
+
+    impl ToValue for DateTime<Utc> {
+        fn serialize(&self) -> Bytes {
+            self.naive_utc().serialize()
+        }
+    }
+
+    impl FromValue for DateTime<Utc> {
+        type Error = chrono::ParseError;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            NaiveDateTime::deserialize(value).map(|dt| dt.and_utc())
+        }
+    }
+
+    /// Timestamps are normalised to UTC, so deserialised values always have a zero offset
+    impl ToValue for DateTime<FixedOffset> {
+        fn serialize(&self) -> Bytes {
+            self.naive_utc().serialize()
+        }
+    }
+
+    impl FromValue for DateTime<FixedOffset> {
+        type Error = chrono::ParseError;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            DateTime::<Utc>::deserialize(value).map(|dt| dt.fixed_offset())
+        }
+    }
+
+    fn from_epoch(value: Bytes) -> Result<DateTime<Utc>, TimestampOutOfRange> {
+        let (secs, nanos) = deserialize_epoch(value);
+        DateTime::from_timestamp(secs, nanos).ok_or(TimestampOutOfRange)
+    }
+
+    /// Dates are stored as the timestamp of midnight (UTC) at the start of the day
+    impl ToValue for Epoch<NaiveDate> {
+        fn serialize(&self) -> Bytes {
+            Epoch(self.0.and_time(NaiveTime::MIN)).serialize()
+        }
+    }
+
+    impl FromValue for Epoch<NaiveDate> {
+        type Error = TimestampOutOfRange;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            from_epoch(value).map(|dt| Epoch(dt.date_naive()))
+        }
+    }
+
+    /// Naive timestamps are assumed to be in UTC
+    impl ToValue for Epoch<NaiveDateTime> {
+        fn serialize(&self) -> Bytes {
+            Epoch(self.0.and_utc()).serialize()
+        }
+    }
+
+    impl FromValue for Epoch<NaiveDateTime> {
+        type Error = TimestampOutOfRange;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            from_epoch(value).map(|dt| Epoch(dt.naive_utc()))
+        }
+    }
+
+    impl ToValue for Epoch<DateTime<Utc>> {
+        fn serialize(&self) -> Bytes {
+            serialize_epoch(self.0.timestamp(), self.0.timestamp_subsec_nanos())
+        }
+    }
+
+    impl FromValue for Epoch<DateTime<Utc>> {
+        type Error = TimestampOutOfRange;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            from_epoch(value).map(Epoch)
+        }
+    }
+
+    impl ToValue for Epoch<DateTime<FixedOffset>> {
+        fn serialize(&self) -> Bytes {
+            Epoch(self.0.with_timezone(&Utc)).serialize()
+        }
+    }
+
+    impl FromValue for Epoch<DateTime<FixedOffset>> {
+        type Error = TimestampOutOfRange;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            from_epoch(value).map(|dt| Epoch(dt.fixed_offset()))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_values {
+    use super::{deserialize_epoch, serialize_epoch, Epoch};
+    use crate::{FromValue, ToValue};
+
+    use bytes::Bytes;
+    use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    /// Timestamps are normalised to UTC, using the same format as `chrono::DateTime<Utc>`
+    impl ToValue for OffsetDateTime {
+        fn serialize(&self) -> Bytes {
+            let dt = self.to_offset(UtcOffset::UTC);
+            format!(
+                "{:04}{:02}{:02}{:02}{:02}{:02}",
+                dt.year(),
+                u8::from(dt.month()),
+                dt.day(),
+                dt.hour(),
+                dt.minute(),
+                dt.second()
+            )
+            .into()
+        }
+    }
+
+    impl FromValue for OffsetDateTime {
+        type Error = time::error::Parse;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            let format =
+                time::macros::format_description!("[year][month][day][hour][minute][second]");
+            PrimitiveDateTime::parse(&String::from_utf8_lossy(&value), format)
+                .map(PrimitiveDateTime::assume_utc)
+        }
+    }
+
+    impl ToValue for Epoch<OffsetDateTime> {
+        fn serialize(&self) -> Bytes {
+            serialize_epoch(self.0.unix_timestamp(), self.0.nanosecond())
+        }
+    }
+
+    impl FromValue for Epoch<OffsetDateTime> {
+        type Error = time::error::ComponentRange;
+
+        fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+            let (secs, nanos) = deserialize_epoch(value);
+            OffsetDateTime::from_unix_timestamp(secs)?
+                .replace_nanosecond(nanos)
+                .map(Epoch)
+        }
+    }
+}
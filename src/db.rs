@@ -1,6 +1,10 @@
-use crate::ffi::{self, cxx_bytes, ToCxxString};
+use crate::ffi::{self, cxx_bytes, fallible, ToCxxString};
 
-use std::{path::Path, pin::Pin};
+use std::{
+    fmt::{self, Debug},
+    path::Path,
+    pin::Pin,
+};
 
 use autocxx::{cxx, prelude::*};
 use bitflags::bitflags;
@@ -11,28 +15,474 @@ pub struct Database(Pin<Box<ffi::Database>>);
 
 impl Database {
     /// Open a read-only Database at the provided path
-    pub fn open(path: impl AsRef<Path>, backend: impl Into<Option<DbBackend>>) -> Self {
-        let flags = backend.into().unwrap_or_default();
-        Self(ffi::Database::new1(&path.as_ref().to_cxx_string(), flags.into()).within_box())
+    pub fn open(
+        path: impl AsRef<Path>,
+        backend: impl Into<Option<DbBackend>>,
+    ) -> crate::Result<Self> {
+        Self::open_with_flags(path.as_ref(), backend.into().unwrap_or_default() as i32)
+    }
+
+    pub(crate) fn open_with_flags(path: &Path, flags: i32) -> crate::Result<Self> {
+        let db = fallible::database_open(&path.to_cxx_string(), flags)?;
+        Ok(Self(ffi::shim::database_clone(&db).within_box()))
+    }
+
+    /// Open a single-file database from a file descriptor, which Xapian takes ownership of
+    #[cfg(unix)]
+    pub(crate) fn open_fd(fd: std::os::unix::io::RawFd, flags: i32) -> crate::Result<Self> {
+        let db = fallible::database_open_fd(fd, flags)?;
+        Ok(Self(ffi::shim::database_clone(&db).within_box()))
+    }
+
+    /// Create a `Database` which searches across each of the provided shards
+    ///
+    /// The [`DocId`][crate::DocId]s of the combined database are interleaved across the shards;
+    /// see [`Match::shard_index`][crate::Match::shard_index] and
+    /// [`Match::shard_docid`][crate::Match::shard_docid] to map them back
+    pub fn from_shards(shards: impl IntoIterator<Item = impl Shards>) -> crate::Result<Self> {
+        let mut db = Self(ffi::shim::database_empty().within_box());
+        for shard in shards {
+            db.add_database(shard)?;
+        }
+        Ok(db)
+    }
+
+    /// Add the shards of another database to this one, so that they are searched together
+    pub fn add_database(&mut self, db: impl Shards) -> crate::Result<()> {
+        Ok(fallible::database_add_database(
+            self.0.as_mut(),
+            db.as_ref(),
+        )?)
+    }
+
+    /// Check the integrity of the database (or single database table) at `path`, writing a
+    /// description of any problems found to `out`, and returning them grouped by table
+    ///
+    /// Further details, such as B-tree statistics, can be written by passing the relevant
+    /// [`CheckFlags`][crate::CheckFlags], at the cost of checking the tables twice. Problems which
+    /// prevent the check from completing are returned as an error, such as
+    /// [`Error::DatabaseCorrupt`][crate::Error::DatabaseCorrupt]
+    pub fn check(
+        path: impl AsRef<Path>,
+        flags: impl Into<Option<crate::CheckFlags>>,
+        out: impl std::io::Write,
+    ) -> crate::Result<crate::CheckReport> {
+        crate::check::check(path.as_ref(), flags.into().unwrap_or_default(), out)
     }
 
     /// Close a Database
-    pub fn close(&mut self) {
-        self.0.as_mut().close()
+    pub fn close(&mut self) -> crate::Result<()> {
+        Ok(fallible::database_close(self.0.as_mut())?)
+    }
+
+    /// Write the documents, metadata, spelling and synonym tables of the database to `out` in
+    /// JSON Lines format, one [`DumpRecord`][crate::DumpRecord] per line
+    ///
+    /// The dump can be loaded into a database using any backend via
+    /// [`WritableDatabase::restore`]
+    #[cfg(feature = "serde")]
+    pub fn dump(&self, out: impl std::io::Write) -> crate::Result<()> {
+        crate::dump::dump(self, out)
     }
 
-    /// Get the number of documents stored in the database
-    pub fn doc_count(&self) -> u32 {
-        self.0.get_doccount().into()
+    /// Reopen the database at its latest revision, making any newly committed changes visible
+    ///
+    /// Returns `true` if the database may have been reopened at a newer revision
+    pub fn reopen(&mut self) -> crate::Result<bool> {
+        Ok(fallible::database_reopen(self.0.as_mut())?)
+    }
+
+    /// Run `f`, reopening the database and retrying whenever it fails with
+    /// [`Error::DatabaseModified`][crate::Error::DatabaseModified]
+    ///
+    /// This happens when the revision being read is overwritten by a concurrent writer, such as
+    /// while iterating over an [`MSet`][crate::MSet]. `f` should therefore perform the entire
+    /// search, including any reads of the matching documents.
+    /// At most `max_retries` (3 if `None`) retries are attempted before the error is returned
+    pub fn retry_on_modified<T>(
+        &mut self,
+        max_retries: impl Into<Option<u32>>,
+        mut f: impl FnMut(&Database) -> crate::Result<T>,
+    ) -> crate::Result<T> {
+        let mut retries = max_retries.into().unwrap_or(3);
+        loop {
+            match f(self) {
+                Err(crate::Error::DatabaseModified(_)) if retries > 0 => {
+                    retries -= 1;
+                    self.reopen()?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Run `enquire`'s search via [`Enquire::mset`][crate::Enquire::mset], passing the matches to
+    /// `f`, and reopening the database and re-running the search whenever either fails with
+    /// [`Error::DatabaseModified`][crate::Error::DatabaseModified]
+    ///
+    /// `enquire` must search this database or a clone of it, which shares its revision, so that
+    /// the search is re-run against the reopened database. As with [`Database::retry_on_modified`],
+    /// `f` should read everything it needs from the matches, and at most `max_retries` (3 if
+    /// `None`) retries are attempted
+    pub fn retry_search<T>(
+        &mut self,
+        enquire: &crate::Enquire,
+        first: u32,
+        maxitems: u32,
+        max_retries: impl Into<Option<u32>>,
+        mut f: impl FnMut(crate::MSet) -> crate::Result<T>,
+    ) -> crate::Result<T> {
+        self.retry_on_modified(max_retries, |_| {
+            f(enquire.mset(first, maxitems, None, None)?)
+        })
+    }
+
+    /// Get the number of shards which make up this database
+    ///
+    /// Each database listed by a stub database file counts as a separate shard
+    pub fn shard_count(&self) -> u32 {
+        Shards::shard_count(self)
     }
 
     /// Detect whether a given term exists in the database
-    pub fn term_exists(&self, term: impl AsRef<[u8]>) -> bool {
+    pub fn term_exists(&self, term: impl AsRef<[u8]>) -> crate::Result<bool> {
         cxx::let_cxx_string!(term = term);
-        self.0.term_exists(&term)
+        Ok(fallible::database_term_exists(&self.0, &term)?)
     }
 }
 
+/// The read-only methods shared by [`Database`] and [`WritableDatabase`]
+macro_rules! read_methods {
+    ($t:ty) => {
+        impl $t {
+            fn db(&self) -> &ffi::Database {
+                self.as_ref()
+            }
+
+            /// Get an iterator over all terms in the database which start with `prefix`
+            ///
+            /// An empty prefix iterates over every term in the database
+            pub fn allterms(
+                &self,
+                prefix: impl AsRef<str>,
+            ) -> crate::Result<crate::iter::TermIter> {
+                let db = self.db();
+                cxx::let_cxx_string!(prefix = prefix.as_ref());
+                let begin = fallible::database_allterms_begin(db, &prefix)?;
+                Ok(crate::iter::TermIter::with_database(
+                    ffi::shim::term_iterator_copy(&begin).within_box(),
+                    db.allterms_end(&prefix).within_box(),
+                    ffi::shim::database_clone(db).within_box(),
+                ))
+            }
+
+            /// Get the average length of the documents in the database
+            pub fn avlength(&self) -> crate::Result<f64> {
+                Ok(fallible::database_get_avlength(self.db())?)
+            }
+
+            /// Get the total number of occurrences of `term` across all documents in the database
+            pub fn collection_freq(&self, term: impl AsRef<[u8]>) -> crate::Result<u32> {
+                cxx::let_cxx_string!(term = term);
+                Ok(fallible::database_get_collection_freq(self.db(), &term)?)
+            }
+
+            /// Write a compacted copy of this database to `dest`
+            ///
+            /// See [`CompactOptions`][crate::CompactOptions] for details on the available options
+            pub fn compact(
+                &self,
+                dest: impl AsRef<Path>,
+                options: impl Into<Option<crate::CompactOptions>>,
+            ) -> crate::Result<()> {
+                crate::compact::compact(
+                    self.db(),
+                    dest.as_ref(),
+                    options.into().unwrap_or_default(),
+                )
+            }
+
+            /// Get the number of documents stored in the database
+            pub fn doc_count(&self) -> crate::Result<u32> {
+                Ok(fallible::database_get_doccount(self.db())?)
+            }
+
+            /// Get the length of the document matching the specified [`DocId`][crate::DocId]
+            pub fn doclength(&self, id: impl Into<crate::DocId>) -> crate::Result<u32> {
+                let id: crate::DocId = id.into();
+                Ok(fallible::database_get_doclength(self.db(), id.into())?)
+            }
+
+            /// Get a lower bound on the length of the documents in the database
+            pub fn doclength_lower_bound(&self) -> crate::Result<u32> {
+                Ok(fallible::database_get_doclength_lower_bound(self.db())?)
+            }
+
+            /// Get an upper bound on the length of the documents in the database
+            pub fn doclength_upper_bound(&self) -> crate::Result<u32> {
+                Ok(fallible::database_get_doclength_upper_bound(self.db())?)
+            }
+
+            /// Retrieve the document matching the specified [`DocId`][crate::DocId]
+            pub fn document(&self, id: impl Into<crate::DocId>) -> crate::Result<crate::Document> {
+                let id: crate::DocId = id.into();
+                let doc = fallible::database_get_document(self.db(), id.into())?;
+                Ok(crate::Document::new(
+                    ffi::shim::document_copy(&doc).within_box(),
+                ))
+            }
+
+            /// Retrieve the documents matching each of the specified [`DocId`][crate::DocId]s, in
+            /// order
+            ///
+            /// Fails if any of the documents do not exist
+            pub fn documents(
+                &self,
+                ids: impl IntoIterator<Item = crate::DocId>,
+            ) -> crate::Result<Vec<crate::Document>> {
+                ids.into_iter().map(|id| self.document(id)).collect()
+            }
+
+            /// Detect whether a document with the specified [`DocId`][crate::DocId] exists in the
+            /// database
+            pub fn has_document(&self, id: impl Into<crate::DocId>) -> crate::Result<bool> {
+                let id: crate::DocId = id.into();
+                Ok(fallible::database_has_document(self.db(), id.into())?)
+            }
+
+            /// Detect whether the database contains any positional information
+            pub fn has_positions(&self) -> crate::Result<bool> {
+                Ok(fallible::database_has_positions(self.db())?)
+            }
+
+            /// Get the highest [`DocId`][crate::DocId] which has been used in the database
+            ///
+            /// Returns `None` if no documents have ever been added
+            pub fn last_docid(&self) -> crate::Result<Option<crate::DocId>> {
+                Ok(crate::DocId::new(fallible::database_get_lastdocid(
+                    self.db(),
+                )?))
+            }
+
+            /// Get the user-specified metadata associated with a given key
+            ///
+            /// Returns an empty buffer if there is no metadata associated with `key`
+            pub fn metadata(&self, key: impl AsRef<[u8]>) -> crate::Result<Bytes> {
+                cxx::let_cxx_string!(key = key);
+                let value = fallible::database_get_metadata(self.db(), &key)?;
+                Ok(cxx_bytes(&value))
+            }
+
+            /// Get the user-specified metadata associated with a given key, deserialized via
+            /// [`FromValue`][crate::FromValue]
+            ///
+            /// Returns `Ok(None)` when there is no metadata associated with `key`, and an
+            /// [`Error::Serialisation`][crate::Error::Serialisation] if it can't be deserialized
+            pub fn metadata_as<T: crate::FromValue>(
+                &self,
+                key: impl AsRef<[u8]>,
+            ) -> crate::Result<Option<T>> {
+                let key = key.as_ref();
+                let value = self.metadata(key)?;
+                if value.is_empty() {
+                    return Ok(None);
+                }
+                T::deserialize(value).map(Some).map_err(|e| {
+                    crate::Error::Serialisation(crate::ErrorInfo::new(format!(
+                        "invalid metadata for key `{}`: {e}",
+                        String::from_utf8_lossy(key)
+                    )))
+                })
+            }
+
+            /// Get an iterator over all metadata keys which start with `prefix`
+            ///
+            /// An empty prefix iterates over every metadata key in the database
+            pub fn metadata_keys(
+                &self,
+                prefix: impl AsRef<str>,
+            ) -> crate::Result<crate::iter::TermIter> {
+                let db = self.db();
+                cxx::let_cxx_string!(prefix = prefix.as_ref());
+                let begin = fallible::database_metadata_keys_begin(db, &prefix)?;
+                Ok(crate::iter::TermIter::new(
+                    ffi::shim::term_iterator_copy(&begin).within_box(),
+                    db.metadata_keys_end(&prefix).within_box(),
+                    crate::iter::TermStats::None,
+                ))
+            }
+
+            /// Get an iterator over the documents indexed by `term`
+            ///
+            /// Every document is indexed by the empty term, so passing `""` iterates over all
+            /// documents
+            pub fn postings(
+                &self,
+                term: impl AsRef<[u8]>,
+            ) -> crate::Result<crate::iter::PostingIter> {
+                let db = self.db();
+                cxx::let_cxx_string!(term = term);
+                let begin = fallible::database_postlist_begin(db, &term)?;
+                Ok(crate::iter::PostingIter::new(
+                    ffi::shim::posting_iterator_copy(&begin).within_box(),
+                    db.postlist_end(&term).within_box(),
+                    !term.is_empty(),
+                ))
+            }
+
+            /// Get the revision of the database which is currently open
+            ///
+            /// The revision increases with every commit, but is not meaningful across databases
+            pub fn revision(&self) -> crate::Result<u64> {
+                Ok(fallible::database_get_revision(self.db())?)
+            }
+
+            /// Suggest a spelling correction for `word`, using the database's spelling dictionary
+            ///
+            /// Only corrections within `max_edit_distance` edits (2 if `None`) of `word` are
+            /// considered. Returns `None` if no suggestion could be found
+            pub fn spelling_suggestion(
+                &self,
+                word: impl AsRef<str>,
+                max_edit_distance: impl Into<Option<u32>>,
+            ) -> crate::Result<Option<String>> {
+                cxx::let_cxx_string!(word = word.as_ref());
+                let suggestion = fallible::database_get_spelling_suggestion(
+                    self.db(),
+                    &word,
+                    max_edit_distance.into().unwrap_or(2),
+                )?;
+                Ok((!suggestion.is_empty()).then(|| suggestion.to_string()))
+            }
+
+            /// Get an iterator over the words in the spelling dictionary
+            ///
+            /// The frequency of each word is available via
+            /// [`Term::frequency`][crate::Term::frequency]
+            pub fn spellings(&self) -> crate::Result<crate::iter::TermIter> {
+                let db = self.db();
+                let begin = fallible::database_spellings_begin(db)?;
+                Ok(crate::iter::TermIter::new(
+                    ffi::shim::term_iterator_copy(&begin).within_box(),
+                    db.spellings_end().within_box(),
+                    crate::iter::TermStats::Frequency,
+                ))
+            }
+
+            /// Get an iterator over all terms which have synonyms and start with `prefix`
+            ///
+            /// An empty prefix iterates over every such term in the database
+            pub fn synonym_keys(
+                &self,
+                prefix: impl AsRef<str>,
+            ) -> crate::Result<crate::iter::TermIter> {
+                let db = self.db();
+                cxx::let_cxx_string!(prefix = prefix.as_ref());
+                let begin = fallible::database_synonym_keys_begin(db, &prefix)?;
+                Ok(crate::iter::TermIter::new(
+                    ffi::shim::term_iterator_copy(&begin).within_box(),
+                    db.synonym_keys_end(&prefix).within_box(),
+                    crate::iter::TermStats::None,
+                ))
+            }
+
+            /// Get an iterator over the synonyms of `term`
+            pub fn synonyms(&self, term: impl AsRef<[u8]>) -> crate::Result<crate::iter::TermIter> {
+                let db = self.db();
+                cxx::let_cxx_string!(term = term);
+                let begin = fallible::database_synonyms_begin(db, &term)?;
+                Ok(crate::iter::TermIter::new(
+                    ffi::shim::term_iterator_copy(&begin).within_box(),
+                    db.synonyms_end(&term).within_box(),
+                    crate::iter::TermStats::None,
+                ))
+            }
+
+            /// Get the number of documents in the database which are indexed by `term`
+            pub fn termfreq(&self, term: impl AsRef<[u8]>) -> crate::Result<u32> {
+                cxx::let_cxx_string!(term = term);
+                Ok(fallible::database_get_termfreq(self.db(), &term)?)
+            }
+
+            /// Get the total length of all documents in the database
+            pub fn total_length(&self) -> crate::Result<u64> {
+                Ok(fallible::database_get_total_length(self.db())?)
+            }
+
+            /// Get the number of unique terms in the document matching the specified
+            /// [`DocId`][crate::DocId]
+            pub fn unique_terms(&self, id: impl Into<crate::DocId>) -> crate::Result<u32> {
+                let id: crate::DocId = id.into();
+                Ok(fallible::database_get_unique_terms(self.db(), id.into())?)
+            }
+
+            /// Get the UUID of the database, which is generated when the database is created
+            ///
+            /// Returns `None` for databases which do not support UUIDs, such as in-memory databases
+            pub fn uuid(&self) -> crate::Result<Option<String>> {
+                let uuid = fallible::database_get_uuid(self.db())?;
+                Ok((!uuid.is_empty()).then(|| uuid.to_string()))
+            }
+
+            /// Get the number of documents which have a value stored in `slot`
+            pub fn value_freq(&self, slot: impl Into<crate::Slot>) -> crate::Result<u32> {
+                let slot: crate::Slot = slot.into();
+                Ok(fallible::database_get_value_freq(self.db(), slot.into())?)
+            }
+
+            /// Get a lower bound on the values stored in `slot`
+            ///
+            /// Returns an empty buffer if no values are stored in `slot`
+            pub fn value_lower_bound(&self, slot: impl Into<crate::Slot>) -> crate::Result<Bytes> {
+                let slot: crate::Slot = slot.into();
+                let value = fallible::database_get_value_lower_bound(self.db(), slot.into())?;
+                Ok(cxx_bytes(&value))
+            }
+
+            /// Get an upper bound on the values stored in `slot`
+            ///
+            /// Returns an empty buffer if no values are stored in `slot`
+            pub fn value_upper_bound(&self, slot: impl Into<crate::Slot>) -> crate::Result<Bytes> {
+                let slot: crate::Slot = slot.into();
+                let value = fallible::database_get_value_upper_bound(self.db(), slot.into())?;
+                Ok(cxx_bytes(&value))
+            }
+
+            /// Get an iterator over the values stored in `slot`, along with the
+            /// [`DocId`][crate::DocId] of the document each belongs to
+            pub fn values(
+                &self,
+                slot: impl Into<crate::Slot>,
+            ) -> crate::Result<crate::iter::ValueIter> {
+                let db = self.db();
+                let slot: crate::Slot = slot.into();
+                let begin = fallible::database_valuestream_begin(db, slot.into())?;
+                Ok(crate::iter::ValueIter::new(
+                    ffi::shim::value_iterator_copy(&begin).within_box(),
+                    db.valuestream_end(slot.into()).within_box(),
+                ))
+            }
+
+            /// Get an iterator over the values stored in `slot`, deserialized via
+            /// [`FromValue`][crate::FromValue]
+            pub fn values_as<T: crate::FromValue>(
+                &self,
+                slot: impl Into<crate::Slot>,
+            ) -> crate::Result<crate::iter::TypedValueIter<T>> {
+                Ok(crate::iter::TypedValueIter::new(self.values(slot)?))
+            }
+
+            /// Get an upper bound on the within-document-frequency of `term` in any single document
+            pub fn wdf_upper_bound(&self, term: impl AsRef<[u8]>) -> crate::Result<u32> {
+                cxx::let_cxx_string!(term = term);
+                Ok(fallible::database_get_wdf_upper_bound(self.db(), &term)?)
+            }
+        }
+    };
+}
+
+read_methods!(Database);
+
 impl AsRef<ffi::Database> for Database {
     fn as_ref(&self) -> &ffi::Database {
         &self.0
@@ -59,7 +509,7 @@ impl From<WritableDatabase> for Database {
 
 /// A flag indicating how to handle the database already existing (or not)
 #[repr(i32)]
-#[derive(Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum DbAction {
     /// Open the database if it exists, create it otherwise
     #[default]
@@ -80,7 +530,7 @@ impl From<DbAction> for autocxx::c_int {
 
 /// The type of backend to use for the database
 #[repr(i32)]
-#[derive(Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum DbBackend {
     #[default]
     /// Automatically select a backend
@@ -103,6 +553,7 @@ impl From<DbBackend> for autocxx::c_int {
 
 bitflags! {
     /// Various flags to modify writable database behavior
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
     pub struct DbFlags: u32 {
         /// Don't attempt to ensure changes have hit the disk
         const NO_SYNC = 0x04;
@@ -117,6 +568,175 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// The statistics to read for each [`Posting`], in addition to its [`DocId`][crate::DocId]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct PostingStats: u32 {
+        /// The length of the document
+        const DOCLENGTH = 0x01;
+        /// The positions at which the term occurs in the document
+        const POSITIONS = 0x02;
+        /// The number of unique terms in the document
+        const UNIQUE_TERMS = 0x04;
+        /// The within-document-frequency of the term
+        const WDF = 0x08;
+    }
+}
+
+/// The methods which modify the contents of a database, shared by [`WritableDatabase`] and
+/// [`Transaction`][crate::Transaction], so that a transaction's changes can be made through its
+/// guard without exposing the methods which begin and end transactions
+macro_rules! write_methods {
+    ($t:ty) => {
+        impl $t {
+            /// Add a new document to the database
+            pub fn add_document(
+                &mut self,
+                doc: impl AsRef<ffi::Document>,
+            ) -> crate::Result<crate::DocId> {
+                let id = fallible::writable_database_add_document(
+                    self.database_mut().0.as_mut(),
+                    doc.as_ref(),
+                )?;
+                Ok(unsafe { crate::DocId::new_unchecked(id) })
+            }
+
+            /// Add a word to the spelling dictionary
+            pub fn add_spelling(
+                &self,
+                word: impl AsRef<[u8]>,
+                increment: impl Into<Option<u32>>,
+            ) -> crate::Result<()> {
+                let increment = increment.into().unwrap_or(1);
+                cxx::let_cxx_string!(word = word);
+                Ok(fallible::writable_database_add_spelling(
+                    &self.database().0,
+                    &word,
+                    increment,
+                )?)
+            }
+
+            /// Add a synonym for a term
+            pub fn add_synonym(
+                &self,
+                term: impl AsRef<[u8]>,
+                synonym: impl AsRef<[u8]>,
+            ) -> crate::Result<()> {
+                cxx::let_cxx_string!(term = term);
+                cxx::let_cxx_string!(synonym = synonym);
+                Ok(fallible::writable_database_add_synonym(
+                    &self.database().0,
+                    &term,
+                    &synonym,
+                )?)
+            }
+
+            /// Delete the document (if any) matching the specified [`DocId`][crate::DocId] from the database
+            pub fn delete_document(&mut self, id: impl Into<crate::DocId>) -> crate::Result<()> {
+                let id: crate::DocId = id.into();
+                Ok(fallible::writable_database_delete_document(
+                    self.database_mut().0.as_mut(),
+                    id.into(),
+                )?)
+            }
+
+            /// Delete any documents indexed by the specified term from the database
+            pub fn delete_document_by_term(&mut self, term: impl AsRef<[u8]>) -> crate::Result<()> {
+                cxx::let_cxx_string!(term = term);
+                Ok(fallible::writable_database_delete_document_by_term(
+                    self.database_mut().0.as_mut(),
+                    &term,
+                )?)
+            }
+
+            /// Remove a word from the spelling dictionary
+            pub fn remove_spelling(
+                &self,
+                word: impl AsRef<[u8]>,
+                decrement: impl Into<Option<u32>>,
+            ) -> crate::Result<()> {
+                let decrement = decrement.into().unwrap_or(1);
+                cxx::let_cxx_string!(word = word);
+                Ok(fallible::writable_database_remove_spelling(
+                    &self.database().0,
+                    &word,
+                    decrement,
+                )?)
+            }
+
+            /// Remove the given synonym for the specified term
+            pub fn remove_synonym(
+                &self,
+                term: impl AsRef<[u8]>,
+                synonym: impl AsRef<[u8]>,
+            ) -> crate::Result<()> {
+                cxx::let_cxx_string!(term = term);
+                cxx::let_cxx_string!(synonym = synonym);
+                Ok(fallible::writable_database_remove_synonym(
+                    &self.database().0,
+                    &term,
+                    &synonym,
+                )?)
+            }
+
+            /// Replace the document (if any) matching the specified [`DocId`][crate::DocId] from the database with the specified `doc`
+            pub fn replace_document(
+                &mut self,
+                id: impl Into<crate::DocId>,
+                doc: impl AsRef<ffi::Document>,
+            ) -> crate::Result<()> {
+                let id: crate::DocId = id.into();
+                Ok(fallible::writable_database_replace_document(
+                    self.database_mut().0.as_mut(),
+                    id.into(),
+                    doc.as_ref(),
+                )?)
+            }
+
+            /// Replace any documents matching the given term
+            pub fn replace_document_by_term(
+                &mut self,
+                term: impl AsRef<[u8]>,
+                doc: impl AsRef<ffi::Document>,
+            ) -> crate::Result<u32> {
+                cxx::let_cxx_string!(term = term);
+                Ok(fallible::writable_database_replace_document_by_term(
+                    self.database_mut().0.as_mut(),
+                    &term,
+                    doc.as_ref(),
+                )?)
+            }
+
+            /// Load a dump written by [`Database::dump`] into the database
+            ///
+            /// Documents keep their [`DocId`][crate::DocId]s, replacing any existing documents with
+            /// the same IDs, while spelling frequencies are added to any existing ones. Changes are
+            /// not committed
+            #[cfg(feature = "serde")]
+            pub fn restore(&mut self, input: impl std::io::BufRead) -> crate::Result<()> {
+                crate::dump::restore(self.database_mut(), input)
+            }
+
+            /// Set the user-specified metadata associated with a given key
+            ///
+            /// Setting an empty value removes the metadata associated with `key`
+            pub fn set_metadata(
+                &mut self,
+                key: impl AsRef<[u8]>,
+                value: impl crate::ToValue,
+            ) -> crate::Result<()> {
+                cxx::let_cxx_string!(key = key);
+                cxx::let_cxx_string!(value = value.serialize());
+                Ok(fallible::writable_database_set_metadata(
+                    self.database_mut().0.as_mut(),
+                    &key,
+                    &value,
+                )?)
+            }
+        }
+    };
+}
+
 /// A Xapian database that can be read or written to
 pub struct WritableDatabase(Pin<Box<ffi::WritableDatabase>>);
 
@@ -137,21 +757,17 @@ impl WritableDatabase {
         backend: impl Into<Option<DbBackend>>,
         flags: impl Into<Option<DbFlags>>,
         block_size: impl Into<Option<i32>>,
-    ) -> Self {
+    ) -> crate::Result<Self> {
         let action = action.into().unwrap_or_default();
         let backend = backend.into().unwrap_or_default();
         let flags = flags.into().map(|f| f.bits()).unwrap_or(0);
         let flags = action as i32 | backend as i32 | flags as i32;
+        Self::open_with_flags(path.as_ref(), flags, block_size.into().unwrap_or(0))
+    }
 
-        let block_size = block_size.into().unwrap_or(0);
-        Self(
-            ffi::WritableDatabase::new1(
-                &path.as_ref().to_cxx_string(),
-                flags.into(),
-                block_size.into(),
-            )
-            .within_box(),
-        )
+    pub(crate) fn open_with_flags(path: &Path, flags: i32, block_size: i32) -> crate::Result<Self> {
+        let db = fallible::writable_database_open(&path.to_cxx_string(), flags, block_size)?;
+        Ok(Self(ffi::shim::writable_database_copy(&db).within_box()))
     }
 
     /// Create a new, in-memory WritableDatabase
@@ -159,144 +775,240 @@ impl WritableDatabase {
         Self(ffi::InMemory::open().within_box())
     }
 
-    /// Add shards from another `WritableDatabase`
-    pub fn add_database(&mut self, db: impl AsRef<ffi::WritableDatabase>) {
-        self.0.as_mut().add_database(db.as_ref())
+    // The database modified by the methods shared with `Transaction`
+    fn database(&self) -> &WritableDatabase {
+        self
     }
 
-    /// Add a new document to the database
-    pub fn add_document(&mut self, doc: impl AsRef<ffi::Document>) -> crate::DocId {
-        unsafe { crate::DocId::new_unchecked(self.0.as_mut().add_document(doc.as_ref())) }
+    fn database_mut(&mut self) -> &mut WritableDatabase {
+        self
     }
 
-    /// Add a word to the spelling dictionary
-    pub fn add_spelling(&self, word: impl AsRef<str>, increment: impl Into<Option<u32>>) {
-        let increment = increment.into().unwrap_or(1);
-        cxx::let_cxx_string!(word = word.as_ref());
-        self.0.add_spelling(&word, increment.into())
+    /// Add shards from another `WritableDatabase`
+    pub fn add_database(&mut self, db: impl AsRef<ffi::WritableDatabase>) -> crate::Result<()> {
+        Ok(fallible::writable_database_add_database(
+            self.0.as_mut(),
+            db.as_ref(),
+        )?)
     }
 
-    /// Add a synonym for a term
-    pub fn add_synonym(&self, term: impl AsRef<str>, synonym: impl AsRef<str>) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        cxx::let_cxx_string!(synonym = synonym.as_ref());
-        self.0.add_synonym(&term, &synonym);
+    /// Begin a flushed transaction, returning a guard which cancels it unless it is committed
+    ///
+    /// See [`Transaction`][crate::Transaction] for details
+    pub fn begin(&mut self) -> crate::Result<crate::Transaction<'_>> {
+        crate::Transaction::new(self, true)
     }
 
     /// Begin a transaction
-    pub fn begin_transaction(&mut self, flushed: impl Into<Option<bool>>) {
+    pub fn begin_transaction(&mut self, flushed: impl Into<Option<bool>>) -> crate::Result<()> {
         let flushed = flushed.into().unwrap_or(true);
-        self.0.as_mut().begin_transaction(flushed)
+        Ok(fallible::writable_database_begin_transaction(
+            self.0.as_mut(),
+            flushed,
+        )?)
     }
 
     /// Abort the transaction currently in progress
-    pub fn cancel_transaction(&mut self) {
-        self.0.as_mut().cancel_transaction()
+    pub fn cancel_transaction(&mut self) -> crate::Result<()> {
+        Ok(fallible::writable_database_cancel_transaction(
+            self.0.as_mut(),
+        )?)
     }
 
     /// Complete the transaction currently in progress
-    pub fn commit_transaction(&mut self) {
-        self.0.as_mut().commit_transaction()
+    pub fn commit_transaction(&mut self) -> crate::Result<()> {
+        Ok(fallible::writable_database_commit_transaction(
+            self.0.as_mut(),
+        )?)
     }
 
     /// Close the database
-    pub fn close(&mut self) {
+    pub fn close(&mut self) -> crate::Result<()> {
         let db: Pin<&mut ffi::Database> = unsafe { ffi::upcast(self.0.as_mut()) };
-        db.close()
+        Ok(fallible::database_close(db)?)
     }
 
     /// Commit any pending modifications made to the database
-    pub fn commit(&mut self) {
-        self.0.as_mut().commit()
+    pub fn commit(&mut self) -> crate::Result<()> {
+        Ok(fallible::writable_database_commit(self.0.as_mut())?)
     }
 
-    /// Delete the document (if any) matching the specified [`DocId`][crate::DocId] from the database
-    pub fn delete_document(&mut self, id: impl Into<crate::DocId>) {
-        self.0.as_mut().delete_document(ffi::docid::from(id.into()))
+    /// Retrieve a read-only `Database` instance backed by this `WritableDatabase`
+    pub fn read_only(&self) -> Database {
+        Database::from(self)
     }
 
-    /// Delete any documents indexed by the specified term from the database
-    pub fn delete_document_by_term(&mut self, term: impl AsRef<str>) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        self.0.as_mut().delete_document1(&term)
+    /// Wrap the function specified in `f` in a transaction
+    ///
+    /// The transaction is committed once `f` returns, or cancelled if `f` panics
+    pub fn transaction(
+        &mut self,
+        flushed: impl Into<Option<bool>>,
+        mut f: impl FnMut(&mut crate::Transaction<'_>),
+    ) -> crate::Result<()> {
+        let mut tx = crate::Transaction::new(self, flushed.into().unwrap_or(true))?;
+        f(&mut tx);
+        tx.commit()
     }
 
-    /// Get the number of documents in the database
-    pub fn doc_count(&self) -> u32 {
-        let db: &ffi::Database = self.as_ref();
-        db.get_doccount().into()
+    /// Run `f` in a flushed transaction, committing it if `f` returns `Ok` and cancelling it if
+    /// `f` returns `Err` or panics
+    pub fn try_transaction<T, E: From<crate::Error>>(
+        &mut self,
+        f: impl FnOnce(&mut crate::Transaction<'_>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut tx = self.begin()?;
+        let value = f(&mut tx)?;
+        tx.commit()?;
+        Ok(value)
     }
+}
 
-    /// Get the user-specified metadata associated with a given key
-    pub fn metadata(&self, key: impl AsRef<str>) -> Bytes {
-        let db: &ffi::Database = self.as_ref();
-        cxx::let_cxx_string!(key = key.as_ref());
-        cxx_bytes(&db.get_metadata(&key))
+read_methods!(WritableDatabase);
+write_methods!(WritableDatabase);
+write_methods!(crate::Transaction<'_>);
+
+impl AsRef<ffi::Database> for WritableDatabase {
+    fn as_ref(&self) -> &ffi::Database {
+        ffi::shim::writable_database_upcast(&self.0)
     }
+}
 
-    /// Retrieve a read-only `Database` instance backed by this `WritableDatabase`
-    pub fn read_only(&self) -> Database {
-        Database::from(self)
+impl AsRef<ffi::WritableDatabase> for WritableDatabase {
+    fn as_ref(&self) -> &ffi::WritableDatabase {
+        &self.0
+    }
+}
+
+/// A database made up of one or more shards, such as a [`Database`] or [`WritableDatabase`]
+///
+/// The number of shards is needed to combine databases, and to map the [`DocId`][crate::DocId]s
+/// of search results back to the shards they came from
+pub trait Shards: AsRef<ffi::Database> {
+    /// Get the number of shards which make up this database
+    ///
+    /// Each database listed by a stub database file counts as a separate shard
+    fn shard_count(&self) -> u32 {
+        ffi::shim::database_size(self.as_ref()) as u32
     }
+}
+
+impl Shards for Database {}
+
+impl Shards for WritableDatabase {}
 
-    /// Remove a word from the spelling dictionary
-    pub fn remove_spelling(&self, word: impl AsRef<str>, decrement: impl Into<Option<u32>>) {
-        let decrement = decrement.into().unwrap_or(1);
-        cxx::let_cxx_string!(word = word.as_ref());
-        self.0.remove_spelling(&word, decrement.into());
+impl<T: Shards> Shards for &T {}
+
+/// An individual entry in a term's posting list, with access to position and frequency information
+///
+/// Only the [`DocId`][crate::DocId] is read by default. Other statistics must be requested with
+/// [`PostingIter::with_stats`][crate::iter::PostingIter::with_stats], since they can only be read
+/// before the iterator moves on; those which were not requested return an
+/// [`Error::InvalidOperation`][crate::Error::InvalidOperation]
+pub struct Posting {
+    value: ffi::docid,
+    doclength: crate::Result<u32>,
+    unique_terms: crate::Result<u32>,
+    wdf: crate::Result<u32>,
+    positions: crate::Result<Vec<u32>>,
+}
+
+impl Posting {
+    pub(crate) fn new(
+        ptr: &ffi::PostingIterator,
+        stats: PostingStats,
+        positional: bool,
+    ) -> crate::Result<Self> {
+        type Getter = fn(&ffi::PostingIterator) -> Result<u32, cxx::Exception>;
+        let read = |stat: PostingStats, name: &str, get: Getter| match stats.contains(stat) {
+            true => get(ptr).map_err(crate::Error::from),
+            false => Err(unrequested(name)),
+        };
+
+        let positions = match (stats.contains(PostingStats::POSITIONS), positional) {
+            (false, _) => Err(unrequested("POSITIONS")),
+            (true, false) => Err(crate::Error::InvalidOperation(crate::ErrorInfo::new(
+                "the all-documents posting list has no positional information",
+            ))),
+            (true, true) => fallible::posting_iterator_positionlist_begin(ptr)
+                .map_err(crate::Error::from)
+                .and_then(|begin| {
+                    crate::iter::PositionIter::read(
+                        ffi::shim::position_iterator_copy(&begin).within_box(),
+                        ptr.positionlist_end().within_box(),
+                    )
+                }),
+        };
+
+        Ok(Self {
+            value: fallible::posting_iterator_docid(ptr)?,
+            doclength: read(
+                PostingStats::DOCLENGTH,
+                "DOCLENGTH",
+                fallible::posting_iterator_get_doclength,
+            ),
+            unique_terms: read(
+                PostingStats::UNIQUE_TERMS,
+                "UNIQUE_TERMS",
+                fallible::posting_iterator_get_unique_terms,
+            ),
+            wdf: read(PostingStats::WDF, "WDF", fallible::posting_iterator_get_wdf),
+            positions,
+        })
+    }
+
+    /// Retrieve the [`DocId`][crate::DocId] associated with this posting
+    pub fn docid(&self) -> crate::DocId {
+        unsafe { crate::DocId::new_unchecked(self.value) }
+    }
+
+    /// Get the length of the document associated with this posting
+    ///
+    /// Requires [`PostingStats::DOCLENGTH`]
+    pub fn doclength(&self) -> crate::Result<u32> {
+        self.doclength.clone()
     }
 
-    /// Remove the given synonym for the specified term
-    pub fn remove_synonym(&self, term: impl AsRef<str>, synonym: impl AsRef<str>) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        cxx::let_cxx_string!(synonym = synonym.as_ref());
-        self.0.remove_synonym(&term, &synonym);
+    /// Get the number of occurrences of the term within the document
+    ///
+    /// Requires [`PostingStats::POSITIONS`]
+    pub fn positions_len(&self) -> crate::Result<u32> {
+        Ok(self.positions.as_ref().map_err(Clone::clone)?.len() as u32)
     }
 
-    /// Replace the document (if any) matching the specified [`DocId`][crate::DocId] from the database with the specified `doc`
-    pub fn replace_document(
-        &mut self,
-        id: impl Into<crate::DocId>,
-        doc: impl AsRef<ffi::Document>,
-    ) {
-        self.0
-            .as_mut()
-            .replace_document(ffi::docid::from(id.into()), doc.as_ref())
+    /// Get an iterator over the specific occurrences of the term within the document
+    ///
+    /// Requires [`PostingStats::POSITIONS`]
+    pub fn positions(&self) -> crate::Result<crate::iter::PositionIter> {
+        let positions = self.positions.as_ref().map_err(Clone::clone)?;
+        Ok(crate::iter::PositionIter::new(positions.clone()))
     }
 
-    /// Replace any documents matching the given term
-    pub fn replace_document_by_term(
-        &mut self,
-        term: impl AsRef<str>,
-        doc: impl AsRef<ffi::Document>,
-    ) -> u32 {
-        cxx::let_cxx_string!(term = term.as_ref());
-        self.0
-            .as_mut()
-            .replace_document1(&term, doc.as_ref())
-            .into()
+    /// Get the number of unique terms in the document associated with this posting
+    ///
+    /// Requires [`PostingStats::UNIQUE_TERMS`]
+    pub fn unique_terms(&self) -> crate::Result<u32> {
+        self.unique_terms.clone()
     }
 
-    /// Wrap the function specified in `f` in a transaction
-    pub fn transaction(
-        &mut self,
-        flushed: impl Into<Option<bool>>,
-        mut f: impl FnMut(&mut WritableDatabase),
-    ) {
-        self.begin_transaction(flushed);
-        f(self);
-        self.commit_transaction();
+    /// Get the within-document-frequency of the term for this posting
+    ///
+    /// Requires [`PostingStats::WDF`]
+    pub fn wdf(&self) -> crate::Result<u32> {
+        self.wdf.clone()
     }
 }
 
-impl AsRef<ffi::Database> for WritableDatabase {
-    fn as_ref(&self) -> &ffi::Database {
-        ffi::shim::writable_database_upcast(&self.0)
-    }
+fn unrequested(stat: &str) -> crate::Error {
+    crate::Error::InvalidOperation(crate::ErrorInfo::new(format!(
+        "PostingStats::{stat} was not requested for this posting list"
+    )))
 }
 
-impl AsRef<ffi::WritableDatabase> for WritableDatabase {
-    fn as_ref(&self) -> &ffi::WritableDatabase {
-        &self.0
+impl Debug for Posting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Posting")
+            .field("docid", &self.docid())
+            .finish()
     }
 }
@@ -1,4 +1,4 @@
-use crate::ffi;
+use crate::ffi::{self, fallible};
 
 use std::{
     fmt::{self, Debug, Display},
@@ -17,34 +17,39 @@ impl Document {
     }
 
     /// Add a boolean term to the document
-    pub fn add_boolean_term(&mut self, term: impl AsRef<str>) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        self.0.as_mut().add_boolean_term(&term)
+    pub fn add_boolean_term(&mut self, term: impl AsRef<[u8]>) -> crate::Result<()> {
+        cxx::let_cxx_string!(term = term);
+        Ok(fallible::document_add_boolean_term(self.0.as_mut(), &term)?)
     }
 
     /// Add an occurrence of `term` at the position given by `pos`
     pub fn add_posting(
         &mut self,
-        term: impl AsRef<str>,
+        term: impl AsRef<[u8]>,
         pos: ffi::termpos,
         increment: impl Into<Option<ffi::termcount>>,
-    ) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        self.0
-            .as_mut()
-            .add_posting(&term, pos, increment.into().unwrap_or(1.into()))
+    ) -> crate::Result<()> {
+        cxx::let_cxx_string!(term = term);
+        Ok(fallible::document_add_posting(
+            self.0.as_mut(),
+            &term,
+            pos.into(),
+            increment.into().unwrap_or(1.into()).into(),
+        )?)
     }
 
     /// Add a term to the document, without positional information
     pub fn add_term(
         &mut self,
-        term: impl AsRef<str>,
+        term: impl AsRef<[u8]>,
         increment: impl Into<Option<ffi::termcount>>,
-    ) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        self.0
-            .as_mut()
-            .add_term(&term, increment.into().unwrap_or(1.into()))
+    ) -> crate::Result<()> {
+        cxx::let_cxx_string!(term = term);
+        Ok(fallible::document_add_term(
+            self.0.as_mut(),
+            &term,
+            increment.into().unwrap_or(1.into()).into(),
+        )?)
     }
 
     /// Remove all terms and postings from the document
@@ -52,9 +57,29 @@ impl Document {
         self.0.as_mut().clear_terms()
     }
 
+    /// Remove all values from the document
+    pub fn clear_values(&mut self) {
+        self.0.as_mut().clear_values()
+    }
+
     /// Get the data blob stored in this document
-    pub fn data(&self) -> Bytes {
-        ffi::cxx_bytes(&self.0.get_data())
+    pub fn data(&self) -> crate::Result<Bytes> {
+        Ok(ffi::cxx_bytes(&fallible::document_get_data(&self.0)?))
+    }
+
+    /// Estimate the size (in bytes) of this document, from the length of its data and a rough
+    /// allowance for each term and value, without serialising it
+    pub(crate) fn estimated_len(&self) -> crate::Result<usize> {
+        const ENTRY_ESTIMATE: usize = 16;
+        let entries = self.termlist_count()? as usize + self.values_count()? as usize;
+        Ok(fallible::document_get_data(&self.0)?.len() + entries * ENTRY_ESTIMATE)
+    }
+
+    /// Deserialise a document previously serialised by [`Document::to_bytes`]
+    pub fn from_bytes(data: impl AsRef<[u8]>) -> crate::Result<Self> {
+        cxx::let_cxx_string!(data = data);
+        let doc = fallible::document_unserialise(&data)?;
+        Ok(Self(ffi::shim::document_copy(&doc).within_box()))
     }
 
     /// Get the document ID (if any) associated with this document
@@ -62,10 +87,65 @@ impl Document {
         crate::DocId::new(self.0.get_docid())
     }
 
+    /// Remove the occurrence of `term` at the position given by `pos`, decreasing its
+    /// within-document-frequency by `decrement` (1 if `None`)
+    ///
+    /// Returns an [`Error::InvalidArgument`][crate::Error::InvalidArgument] if `term` does not
+    /// occur at `pos`
+    pub fn remove_posting(
+        &mut self,
+        term: impl AsRef<[u8]>,
+        pos: impl Into<crate::Position>,
+        decrement: impl Into<Option<u32>>,
+    ) -> crate::Result<()> {
+        cxx::let_cxx_string!(term = term);
+        let pos: crate::Position = pos.into();
+        Ok(fallible::document_remove_posting(
+            self.0.as_mut(),
+            &term,
+            pos.into(),
+            decrement.into().unwrap_or(1),
+        )?)
+    }
+
+    /// Remove the occurrences of `term` at positions from `start` to `end` (inclusive),
+    /// decreasing its within-document-frequency by `decrement` (1 if `None`) for each
+    ///
+    /// Returns the number of postings which were removed
+    pub fn remove_postings(
+        &mut self,
+        term: impl AsRef<[u8]>,
+        start: impl Into<crate::Position>,
+        end: impl Into<crate::Position>,
+        decrement: impl Into<Option<u32>>,
+    ) -> crate::Result<u32> {
+        cxx::let_cxx_string!(term = term);
+        let (start, end): (crate::Position, crate::Position) = (start.into(), end.into());
+        Ok(fallible::document_remove_postings(
+            self.0.as_mut(),
+            &term,
+            start.into(),
+            end.into(),
+            decrement.into().unwrap_or(1),
+        )?)
+    }
+
     /// Remove `term` and all postings associated with it from this document
-    pub fn remove_term(&mut self, term: impl AsRef<str>) {
-        cxx::let_cxx_string!(term = term.as_ref());
-        self.0.as_mut().remove_term(&term)
+    pub fn remove_term(&mut self, term: impl AsRef<[u8]>) -> crate::Result<()> {
+        cxx::let_cxx_string!(term = term);
+        Ok(fallible::document_remove_term(self.0.as_mut(), &term)?)
+    }
+
+    /// Remove the value stored in the given slot number
+    ///
+    /// Returns an [`Error::InvalidArgument`][crate::Error::InvalidArgument] if there is no value
+    /// stored in `slot`
+    pub fn remove_value(&mut self, slot: impl Into<crate::Slot>) -> crate::Result<()> {
+        let slot: crate::Slot = slot.into();
+        Ok(fallible::document_remove_value(
+            self.0.as_mut(),
+            slot.into(),
+        )?)
     }
 
     /// Set the data blob stored alongside this document
@@ -80,19 +160,65 @@ impl Document {
     /// To accomodate this, [`ToValue`][crate::ToValue] is used to serialize data in a
     /// Xapian-friendly fashion. This trait is already implemented for most numeric primitives,
     /// string types and byte collections.
-    pub fn set_value(&mut self, slot: impl Into<crate::Slot>, value: impl crate::ToValue) {
+    pub fn set_value(
+        &mut self,
+        slot: impl Into<crate::Slot>,
+        value: impl crate::ToValue,
+    ) -> crate::Result<()> {
+        let slot: crate::Slot = slot.into();
         cxx::let_cxx_string!(value = value.serialize());
-        self.0
-            .as_mut()
-            .add_value(ffi::valueno::from(slot.into()), &value)
+        Ok(fallible::document_add_value(
+            self.0.as_mut(),
+            slot.into(),
+            &value,
+        )?)
     }
 
     /// Retrieve an iterator over the terms in this document
-    pub fn terms(&self) -> crate::iter::TermIter {
-        crate::iter::TermIter::new(
-            self.0.termlist_begin().within_box(),
+    pub fn terms(&self) -> crate::Result<crate::iter::TermIter> {
+        let begin = fallible::document_termlist_begin(&self.0)?;
+        Ok(crate::iter::TermIter::new(
+            ffi::shim::term_iterator_copy(&begin).within_box(),
             self.0.termlist_end().within_box(),
-        )
+            self.term_stats(),
+        ))
+    }
+
+    /// Look up `term` in this document, to access its within-document-frequency and positions
+    ///
+    /// Returns `None` if the document is not indexed by `term`
+    pub fn term(&self, term: impl AsRef<[u8]>) -> crate::Result<Option<crate::Term>> {
+        cxx::let_cxx_string!(term = term);
+        let begin = fallible::document_termlist_begin(&self.0)?;
+        let mut cursor = ffi::shim::term_iterator_copy(&begin).within_box();
+        let end = self.0.termlist_end().within_box();
+        if cursor != end {
+            fallible::term_iterator_skip_to(cursor.as_mut(), &term)?;
+        }
+
+        let found =
+            cursor != end && fallible::term_iterator_term(&cursor)?.as_bytes() == term.as_bytes();
+        found
+            .then(|| crate::Term::new(cursor, self.term_stats(), None))
+            .transpose()
+    }
+
+    /// Term frequencies can only be read from a document which came from a database
+    fn term_stats(&self) -> crate::iter::TermStats {
+        crate::iter::TermStats::Document {
+            frequency: self.id().is_some(),
+        }
+    }
+
+    /// Get the number of distinct terms in this document
+    pub fn termlist_count(&self) -> crate::Result<u32> {
+        Ok(fallible::document_termlist_count(&self.0)?)
+    }
+
+    /// Serialise this document, including its data, terms and values, so that it can be stored
+    /// or transmitted and later restored via [`Document::from_bytes`]
+    pub fn to_bytes(&self) -> crate::Result<Bytes> {
+        Ok(ffi::cxx_bytes(&fallible::document_serialise(&self.0)?))
     }
 
     /// Retrieve the value (if any) stored in the given slot number
@@ -102,18 +228,41 @@ impl Document {
     /// from its Xapian representation. This trait is already implemented for most numeric
     /// primitives, string types and byte collections.
     ///
-    /// Returns `None` when there is no value stored in `slot`
-    /// Returns `Some(Err(T::Error)` when there is a value but deserialization fails
-    /// Returns `Some(Ok(T))` otherwise
+    /// Returns `Ok(None)` when there is no value stored in `slot`
+    /// Returns `Ok(Some(Err(T::Error)))` when there is a value but deserialization fails
+    /// Returns `Ok(Some(Ok(T)))` otherwise
     pub fn value<T: crate::FromValue>(
         &self,
         slot: impl Into<crate::Slot>,
-    ) -> Option<Result<T, T::Error>> {
-        let s = self.0.get_value(ffi::valueno::from(slot.into()));
-        match s.is_empty() {
+    ) -> crate::Result<Option<Result<T, T::Error>>> {
+        let slot: crate::Slot = slot.into();
+        let s = fallible::document_get_value(&self.0, slot.into())?;
+        Ok(match s.is_empty() {
             true => None,
             false => Some(T::deserialize(ffi::cxx_bytes(&s))),
-        }
+        })
+    }
+
+    /// Retrieve an iterator over the values in this document, along with their slot numbers
+    pub fn values(&self) -> crate::Result<crate::iter::DocumentValueIter> {
+        let begin = fallible::document_values_begin(&self.0)?;
+        Ok(crate::iter::DocumentValueIter::new(
+            ffi::shim::value_iterator_copy(&begin).within_box(),
+            self.0.values_end().within_box(),
+        ))
+    }
+
+    /// Retrieve an iterator over the values in this document, deserialized via
+    /// [`FromValue`][crate::FromValue], along with their slot numbers
+    pub fn values_as<T: crate::FromValue>(
+        &self,
+    ) -> crate::Result<crate::iter::TypedDocumentValueIter<T>> {
+        Ok(crate::iter::TypedDocumentValueIter::new(self.values()?))
+    }
+
+    /// Get the number of values stored in this document
+    pub fn values_count(&self) -> crate::Result<u32> {
+        Ok(fallible::document_values_count(&self.0)?)
     }
 }
 
@@ -135,14 +284,57 @@ impl Default for Document {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Document {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, SeqAccess, Visitor};
+
+        struct DocumentVisitor;
+
+        impl<'de> Visitor<'de> for DocumentVisitor {
+            type Value = Document;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a serialised Xapian document")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Document::from_bytes(v).map_err(E::custom)
+            }
+
+            // Formats without a native byte type, such as JSON, represent bytes as a sequence
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        deserializer.deserialize_bytes(DocumentVisitor)
+    }
+}
+
 impl Display for Document {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_fmt(format_args!("{}", self.0.get_data()))
+        let data = fallible::document_get_data(&self.0).map_err(|_| fmt::Error)?;
+        Display::fmt(&*data, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Document {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.to_bytes().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
     }
 }
 
-impl From<crate::Match> for Document {
-    fn from(value: crate::Match) -> Self {
+impl TryFrom<crate::Match> for Document {
+    type Error = crate::Error;
+
+    fn try_from(value: crate::Match) -> Result<Self, Self::Error> {
         value.document()
     }
 }
@@ -0,0 +1,229 @@
+use crate::ffi;
+
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// A blob of bytes in a dump, which is written as a string when it is valid UTF-8, and as an
+/// array of bytes otherwise
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Blob {
+    /// Valid UTF-8 data
+    Text(String),
+    /// Arbitrary binary data, such as a value encoded via [`ToValue`][crate::ToValue]
+    Binary(Vec<u8>),
+}
+
+impl Blob {
+    /// Get the bytes held by this blob
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Blob::Text(s) => s.as_bytes(),
+            Blob::Binary(b) => b,
+        }
+    }
+}
+
+impl From<&[u8]> for Blob {
+    fn from(value: &[u8]) -> Self {
+        match std::str::from_utf8(value) {
+            Ok(s) => Blob::Text(s.to_string()),
+            Err(_) => Blob::Binary(value.to_vec()),
+        }
+    }
+}
+
+impl From<Blob> for Bytes {
+    fn from(value: Blob) -> Self {
+        match value {
+            Blob::Text(s) => s.into(),
+            Blob::Binary(b) => b.into(),
+        }
+    }
+}
+
+/// A term in a [`DumpDocument`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DumpTerm {
+    /// The term itself
+    pub term: Blob,
+    /// The within-document-frequency of the term
+    pub wdf: u32,
+    /// The positions at which the term occurs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub positions: Vec<u32>,
+}
+
+/// A document in a dump
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DumpDocument {
+    /// The document's ID
+    pub id: u32,
+    /// The data blob stored alongside the document
+    pub data: Blob,
+    /// The values stored in the document, by slot number
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<u32, Blob>,
+    /// The terms indexing the document
+    #[serde(default)]
+    pub terms: Vec<DumpTerm>,
+}
+
+/// A single line of a dump written by [`Database::dump`][crate::Database::dump]
+///
+/// Each line is a JSON object, whose `type` field holds the name of the variant in snake case
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DumpRecord {
+    /// A document, with its data, values and terms
+    Document(DumpDocument),
+    /// An entry in the user metadata table
+    Metadata {
+        /// The metadata key
+        key: Blob,
+        /// The value stored under `key`
+        value: Blob,
+    },
+    /// An entry in the spelling dictionary
+    Spelling {
+        /// The word
+        word: Blob,
+        /// The frequency of the word
+        frequency: u32,
+    },
+    /// An entry in the synonym dictionary
+    Synonym {
+        /// The term which has synonyms
+        term: Blob,
+        /// The synonyms of `term`
+        synonyms: Vec<Blob>,
+    },
+}
+
+pub(crate) fn dump(db: &crate::Database, mut out: impl Write) -> crate::Result<()> {
+    for posting in db.postings("")? {
+        let posting = posting?;
+        let doc = db.document(posting.docid())?;
+        let values = doc
+            .values()?
+            .map(|value| {
+                let (slot, value) = value?;
+                Ok((u32::from(slot), Blob::from(value.as_ref())))
+            })
+            .collect::<crate::Result<_>>()?;
+        let terms = doc
+            .terms()?
+            .map(|term| {
+                let term = term?;
+                Ok(DumpTerm {
+                    term: Blob::from(AsRef::<[u8]>::as_ref(&term)),
+                    wdf: term.wdf()?,
+                    positions: term.positions()?.map(u32::from).collect(),
+                })
+            })
+            .collect::<crate::Result<_>>()?;
+
+        write_record(
+            &mut out,
+            &DumpRecord::Document(DumpDocument {
+                id: posting.docid().into(),
+                data: Blob::from(doc.data()?.as_ref()),
+                values,
+                terms,
+            }),
+        )?;
+    }
+
+    for key in db.metadata_keys("")? {
+        let key = key?;
+        let value = Blob::from(db.metadata(&key)?.as_ref());
+        let key = Blob::from(AsRef::<[u8]>::as_ref(&key));
+        write_record(&mut out, &DumpRecord::Metadata { key, value })?;
+    }
+
+    for word in db.spellings()? {
+        let word = word?;
+        let frequency = word.frequency()?;
+        let word = Blob::from(AsRef::<[u8]>::as_ref(&word));
+        write_record(&mut out, &DumpRecord::Spelling { word, frequency })?;
+    }
+
+    for term in db.synonym_keys("")? {
+        let term = term?;
+        let synonyms = db
+            .synonyms(&term)?
+            .map(|synonym| Ok(Blob::from(AsRef::<[u8]>::as_ref(&synonym?))))
+            .collect::<crate::Result<_>>()?;
+        let term = Blob::from(AsRef::<[u8]>::as_ref(&term));
+        write_record(&mut out, &DumpRecord::Synonym { term, synonyms })?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+fn write_record(out: &mut impl Write, record: &DumpRecord) -> crate::Result<()> {
+    serde_json::to_writer(&mut *out, record).map_err(io::Error::from)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+pub(crate) fn restore(db: &mut crate::WritableDatabase, input: impl BufRead) -> crate::Result<()> {
+    for (n, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: DumpRecord = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {e}", n + 1))
+        })?;
+
+        match record {
+            DumpRecord::Document(dumped) => {
+                let Some(id) = crate::DocId::new(dumped.id) else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: document ID must not be 0", n + 1),
+                    )
+                    .into());
+                };
+
+                let mut doc = crate::Document::default();
+                doc.set_data(dumped.data.as_bytes());
+                for (slot, value) in dumped.values {
+                    doc.set_value(slot, Bytes::from(value))?;
+                }
+                for term in dumped.terms {
+                    // Postings don't increase the wdf, so that it can be restored exactly
+                    for pos in term.positions {
+                        doc.add_posting(
+                            term.term.as_bytes(),
+                            pos.into(),
+                            ffi::termcount::from(0u32),
+                        )?;
+                    }
+                    doc.add_term(term.term.as_bytes(), ffi::termcount::from(term.wdf))?;
+                }
+                db.replace_document(id, &doc)?;
+            }
+            DumpRecord::Metadata { key, value } => {
+                db.set_metadata(key.as_bytes(), Bytes::from(value))?
+            }
+            DumpRecord::Spelling { word, frequency } => {
+                db.add_spelling(word.as_bytes(), frequency)?
+            }
+            DumpRecord::Synonym { term, synonyms } => {
+                for synonym in synonyms {
+                    db.add_synonym(term.as_bytes(), synonym.as_bytes())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,319 @@
+use std::{
+    fmt::{self, Display},
+    sync::Arc,
+};
+
+/// A specialized [`Result`][std::result::Result] type for fallible Xapian operations
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Details carried by every [`Error`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorInfo {
+    message: String,
+    context: String,
+    error_string: Option<String>,
+    source: Option<IoSource>,
+}
+
+/// The I/O error behind an [`Error::Io`], shared between clones of the error
+#[derive(Clone, Debug)]
+struct IoSource(Arc<std::io::Error>);
+
+impl PartialEq for IoSource {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+            || (self.0.kind() == other.0.kind() && self.0.to_string() == other.0.to_string())
+    }
+}
+
+impl Eq for IoSource {}
+
+impl ErrorInfo {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The message describing this error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Optional context for this error, such as the remote database which raised it
+    pub fn context(&self) -> Option<&str> {
+        (!self.context.is_empty()).then_some(self.context.as_str())
+    }
+
+    /// A description of the system error (`errno`) associated with this error, if any
+    pub fn error_string(&self) -> Option<&str> {
+        self.error_string.as_deref()
+    }
+}
+
+/// An error raised by Xapian, mirroring the upstream exception hierarchy
+///
+/// See [upstream docs][upstream] for details on each type of error
+///
+/// [upstream]: https://xapian.org/docs/apidoc/html/classXapian_1_1Error.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An assertion in the Xapian library failed (`Xapian::AssertionError`)
+    Assertion(ErrorInfo),
+    /// An invalid argument was supplied (`Xapian::InvalidArgumentError`)
+    InvalidArgument(ErrorInfo),
+    /// An operation was attempted which is not valid for the object's current state
+    /// (`Xapian::InvalidOperationError`)
+    InvalidOperation(ErrorInfo),
+    /// The requested operation is not implemented (`Xapian::UnimplementedError`)
+    Unimplemented(ErrorInfo),
+    /// A generic database error (`Xapian::DatabaseError`)
+    Database(ErrorInfo),
+    /// The database has been closed (`Xapian::DatabaseClosedError`)
+    DatabaseClosed(ErrorInfo),
+    /// The database is corrupt (`Xapian::DatabaseCorruptError`)
+    DatabaseCorrupt(ErrorInfo),
+    /// The database could not be created (`Xapian::DatabaseCreateError`)
+    DatabaseCreate(ErrorInfo),
+    /// The database could not be locked for writing (`Xapian::DatabaseLockError`)
+    DatabaseLock(ErrorInfo),
+    /// The database has been modified since it was opened (`Xapian::DatabaseModifiedError`)
+    DatabaseModified(ErrorInfo),
+    /// The database does not exist (`Xapian::DatabaseNotFoundError`)
+    DatabaseNotFound(ErrorInfo),
+    /// The database could not be opened (`Xapian::DatabaseOpeningError`)
+    DatabaseOpening(ErrorInfo),
+    /// The database uses an unsupported format version (`Xapian::DatabaseVersionError`)
+    DatabaseVersion(ErrorInfo),
+    /// The requested document does not exist (`Xapian::DocNotFoundError`)
+    DocNotFound(ErrorInfo),
+    /// The requested feature is unavailable in this build of Xapian
+    /// (`Xapian::FeatureUnavailableError`)
+    FeatureUnavailable(ErrorInfo),
+    /// An internal Xapian error (`Xapian::InternalError`)
+    Internal(ErrorInfo),
+    /// An error communicating with a remote database (`Xapian::NetworkError`)
+    Network(ErrorInfo),
+    /// A timeout communicating with a remote database (`Xapian::NetworkTimeoutError`)
+    NetworkTimeout(ErrorInfo),
+    /// The query string could not be parsed (`Xapian::QueryParserError`)
+    QueryParser(ErrorInfo),
+    /// An object could not be serialised or unserialised (`Xapian::SerialisationError`)
+    Serialisation(ErrorInfo),
+    /// A value was out of range (`Xapian::RangeError`)
+    Range(ErrorInfo),
+    /// A wildcard expanded to too many terms (`Xapian::WildcardError`)
+    Wildcard(ErrorInfo),
+    /// A C++ exception which is not part of the Xapian hierarchy
+    Other(ErrorInfo),
+    /// An I/O error reading from or writing to a Rust stream, rather than a Xapian exception
+    Io(ErrorInfo),
+}
+
+impl Error {
+    /// An [`Error::InvalidArgument`] raised by xapian-rs itself, rather than by Xapian
+    pub(crate) fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::InvalidArgument(ErrorInfo::new(message))
+    }
+
+    /// The details associated with this error
+    pub fn info(&self) -> &ErrorInfo {
+        use Error::*;
+        match self {
+            Assertion(info)
+            | InvalidArgument(info)
+            | InvalidOperation(info)
+            | Unimplemented(info)
+            | Database(info)
+            | DatabaseClosed(info)
+            | DatabaseCorrupt(info)
+            | DatabaseCreate(info)
+            | DatabaseLock(info)
+            | DatabaseModified(info)
+            | DatabaseNotFound(info)
+            | DatabaseOpening(info)
+            | DatabaseVersion(info)
+            | DocNotFound(info)
+            | FeatureUnavailable(info)
+            | Internal(info)
+            | Network(info)
+            | NetworkTimeout(info)
+            | QueryParser(info)
+            | Serialisation(info)
+            | Range(info)
+            | Wildcard(info)
+            | Other(info)
+            | Io(info) => info,
+        }
+    }
+
+    /// The message describing this error
+    pub fn message(&self) -> &str {
+        self.info().message()
+    }
+
+    /// Optional context for this error, such as the remote database which raised it
+    pub fn context(&self) -> Option<&str> {
+        self.info().context()
+    }
+
+    /// A description of the system error (`errno`) associated with this error, if any
+    pub fn error_string(&self) -> Option<&str> {
+        self.info().error_string()
+    }
+
+    /// The kind of the underlying I/O error, if this is an [`Error::Io`]
+    pub fn io_kind(&self) -> Option<std::io::ErrorKind> {
+        self.info().source.as_ref().map(|source| source.0.kind())
+    }
+
+    /// The name of the upstream Xapian exception type represented by this error
+    pub fn type_name(&self) -> &'static str {
+        use Error::*;
+        match self {
+            Assertion(_) => "AssertionError",
+            InvalidArgument(_) => "InvalidArgumentError",
+            InvalidOperation(_) => "InvalidOperationError",
+            Unimplemented(_) => "UnimplementedError",
+            Database(_) => "DatabaseError",
+            DatabaseClosed(_) => "DatabaseClosedError",
+            DatabaseCorrupt(_) => "DatabaseCorruptError",
+            DatabaseCreate(_) => "DatabaseCreateError",
+            DatabaseLock(_) => "DatabaseLockError",
+            DatabaseModified(_) => "DatabaseModifiedError",
+            DatabaseNotFound(_) => "DatabaseNotFoundError",
+            DatabaseOpening(_) => "DatabaseOpeningError",
+            DatabaseVersion(_) => "DatabaseVersionError",
+            DocNotFound(_) => "DocNotFoundError",
+            FeatureUnavailable(_) => "FeatureUnavailableError",
+            Internal(_) => "InternalError",
+            Network(_) => "NetworkError",
+            NetworkTimeout(_) => "NetworkTimeoutError",
+            QueryParser(_) => "QueryParserError",
+            Serialisation(_) => "SerialisationError",
+            Range(_) => "RangeError",
+            Wildcard(_) => "WildcardError",
+            Other(_) => "Exception",
+            Io(_) => "IoError",
+        }
+    }
+
+    /// Returns `true` if this error represents a programming error (`Xapian::LogicError`)
+    pub fn is_logic_error(&self) -> bool {
+        use Error::*;
+        matches!(
+            self,
+            Assertion(_) | InvalidArgument(_) | InvalidOperation(_) | Unimplemented(_)
+        )
+    }
+
+    /// Returns `true` if this error could only be detected at runtime (`Xapian::RuntimeError`)
+    pub fn is_runtime_error(&self) -> bool {
+        !self.is_logic_error() && !matches!(self, Error::Other(_) | Error::Io(_))
+    }
+
+    /// Returns `true` if this error is a `Xapian::DatabaseError` or one of its subclasses
+    pub fn is_database_error(&self) -> bool {
+        use Error::*;
+        matches!(
+            self,
+            Database(_)
+                | DatabaseClosed(_)
+                | DatabaseCorrupt(_)
+                | DatabaseCreate(_)
+                | DatabaseLock(_)
+                | DatabaseModified(_)
+                | DatabaseNotFound(_)
+                | DatabaseOpening(_)
+                | DatabaseVersion(_)
+        )
+    }
+
+    /// Returns `true` if this error is a `Xapian::DatabaseOpeningError` or one of its subclasses
+    pub fn is_database_opening_error(&self) -> bool {
+        use Error::*;
+        matches!(
+            self,
+            DatabaseNotFound(_) | DatabaseOpening(_) | DatabaseVersion(_)
+        )
+    }
+
+    /// Returns `true` if this error is a `Xapian::NetworkError` or one of its subclasses
+    pub fn is_network_error(&self) -> bool {
+        matches!(self, Error::Network(_) | Error::NetworkTimeout(_))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.type_name(), self.message())?;
+        if let Some(context) = self.context() {
+            write!(f, " (context: {context})")?;
+        }
+        if let Some(error_string) = self.error_string() {
+            write!(f, " ({error_string})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        let source = self.info().source.as_ref()?;
+        Some(&*source.0)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(ErrorInfo {
+            message: value.to_string(),
+            context: String::new(),
+            error_string: Some(value.kind().to_string()),
+            source: Some(IoSource(Arc::new(value))),
+        })
+    }
+}
+
+/// Decode the record produced by the `rust::behavior::trycatch` handler in `cpp/shim.h`
+impl From<cxx::Exception> for Error {
+    fn from(value: cxx::Exception) -> Self {
+        let mut fields = value.what().splitn(4, '\x1f');
+        let kind = fields.next().unwrap_or_default();
+        let info = ErrorInfo {
+            message: fields.next().unwrap_or_default().to_string(),
+            context: fields.next().unwrap_or_default().to_string(),
+            error_string: fields.next().filter(|s| !s.is_empty()).map(String::from),
+            source: None,
+        };
+
+        use Error::*;
+        match kind {
+            "AssertionError" => Assertion(info),
+            "InvalidArgumentError" => InvalidArgument(info),
+            "InvalidOperationError" => InvalidOperation(info),
+            "UnimplementedError" => Unimplemented(info),
+            "DatabaseError" => Database(info),
+            "DatabaseClosedError" => DatabaseClosed(info),
+            "DatabaseCorruptError" => DatabaseCorrupt(info),
+            "DatabaseCreateError" => DatabaseCreate(info),
+            "DatabaseLockError" => DatabaseLock(info),
+            "DatabaseModifiedError" => DatabaseModified(info),
+            "DatabaseNotFoundError" => DatabaseNotFound(info),
+            "DatabaseOpeningError" => DatabaseOpening(info),
+            "DatabaseVersionError" => DatabaseVersion(info),
+            "DocNotFoundError" => DocNotFound(info),
+            "FeatureUnavailableError" => FeatureUnavailable(info),
+            "InternalError" => Internal(info),
+            "NetworkError" => Network(info),
+            "NetworkTimeoutError" => NetworkTimeout(info),
+            "QueryParserError" => QueryParser(info),
+            "SerialisationError" => Serialisation(info),
+            "RangeError" => Range(info),
+            "WildcardError" => Wildcard(info),
+            _ => Other(info),
+        }
+    }
+}
@@ -19,7 +19,7 @@
 use std::{cell::RefCell, fmt::Debug, path::Path, pin::Pin, rc::Rc};
 
 use autocxx::{
-    cxx::{CxxString, UniquePtr},
+    cxx::{CxxString, CxxVector, UniquePtr},
     prelude::*,
     subclass::prelude::*,
 };
@@ -35,6 +35,7 @@ include_cpp! {
     #include "shim.h"
     safety!(unsafe)
 
+    block!("Xapian::Compactor")
     block!("Xapian::DateValueRangeProcessor")
     block!("Xapian::ErrorHandler")
     block!("Xapian::ExpandDecider")
@@ -46,6 +47,7 @@ include_cpp! {
     block!("Xapian::Stopper")
     block!("Xapian::ValueRangeProcessor")
 
+    subclass!("shim::FfiCompactor", RustCompactor)
     subclass!("shim::FfiExpandDecider", RustExpandDecider)
     subclass!("shim::FfiFieldProcessor", RustFieldProcessor)
     subclass!("shim::FfiMatchDecider", RustMatchDecider)
@@ -69,6 +71,7 @@ include_cpp! {
     generate!("Xapian::MSet")
     generate!("Xapian::MSetIterator")
     generate!("Xapian::NumberRangeProcessor")
+    generate!("Xapian::PostingIterator")
     generate!("Xapian::Query")
     generate!("Xapian::QueryParser")
     generate!("Xapian::QueryParser_feature_flag")
@@ -82,6 +85,7 @@ include_cpp! {
     generate!("Xapian::QueryParser_stem_strategy")
     generate!("Xapian::TermIterator")
     generate!("Xapian::ValueCountMatchSpy")
+    generate!("Xapian::ValueIterator")
     generate!("Xapian::WritableDatabase")
 
     generate!("Xapian::InMemory::open")
@@ -89,6 +93,350 @@ include_cpp! {
     generate_ns!("shim")
 }
 
+/// Manual bindings for Xapian calls which may throw, allowing exceptions to be surfaced as a
+/// [`Result`][crate::Result] instead of aborting the process
+#[cxx::bridge(namespace = "fallible")]
+pub mod fallible {
+    #[namespace = "Xapian"]
+    extern "C++" {
+        type Database = super::Database;
+        type Document = super::Document;
+        type Enquire = super::Enquire;
+        type ESet = super::ESet;
+        type MSet = super::MSet;
+        type MSetIterator = super::MSetIterator;
+        type PositionIterator = super::PositionIterator;
+        type PostingIterator = super::PostingIterator;
+        type Query = super::Query;
+        type QueryParser = super::QueryParser;
+        type RSet = super::RSet;
+        type Stem = super::Stem;
+        type TermGenerator = super::TermGenerator;
+        type TermIterator = super::TermIterator;
+        type ValueIterator = super::ValueIterator;
+        type WritableDatabase = super::WritableDatabase;
+    }
+
+    #[namespace = "shim"]
+    extern "C++" {
+        type FfiCompactor = super::shim::FfiCompactor;
+        type FfiExpandDecider = super::shim::FfiExpandDecider;
+        type FfiFieldProcessor = super::shim::FfiFieldProcessor;
+        type FfiMatchDecider = super::shim::FfiMatchDecider;
+    }
+
+    unsafe extern "C++" {
+        include!("shim.h");
+
+        fn database_open(path: &CxxString, flags: i32) -> Result<UniquePtr<Database>>;
+        fn database_open_fd(fd: i32, flags: i32) -> Result<UniquePtr<Database>>;
+        fn database_add_database(db: Pin<&mut Database>, other: &Database) -> Result<()>;
+        fn database_allterms_begin(
+            db: &Database,
+            prefix: &CxxString,
+        ) -> Result<UniquePtr<TermIterator>>;
+        fn database_check(
+            path: &CxxString,
+            opts: i32,
+            output: Pin<&mut CxxString>,
+        ) -> Result<usize>;
+        fn database_close(db: Pin<&mut Database>) -> Result<()>;
+        unsafe fn database_compact(
+            db: &Database,
+            output: &CxxString,
+            flags: u32,
+            block_size: i32,
+            compactor: *mut FfiCompactor,
+        ) -> Result<()>;
+        fn database_get_avlength(db: &Database) -> Result<f64>;
+        fn database_get_collection_freq(db: &Database, term: &CxxString) -> Result<u32>;
+        fn database_get_doccount(db: &Database) -> Result<u32>;
+        fn database_get_doclength(db: &Database, id: u32) -> Result<u32>;
+        fn database_get_doclength_lower_bound(db: &Database) -> Result<u32>;
+        fn database_get_doclength_upper_bound(db: &Database) -> Result<u32>;
+        fn database_get_document(db: &Database, id: u32) -> Result<UniquePtr<Document>>;
+        fn database_get_lastdocid(db: &Database) -> Result<u32>;
+        fn database_get_metadata(db: &Database, key: &CxxString) -> Result<UniquePtr<CxxString>>;
+        fn database_get_revision(db: &Database) -> Result<u64>;
+        fn database_get_spelling_suggestion(
+            db: &Database,
+            word: &CxxString,
+            max_edit_distance: u32,
+        ) -> Result<UniquePtr<CxxString>>;
+        fn database_get_termfreq(db: &Database, term: &CxxString) -> Result<u32>;
+        fn database_get_total_length(db: &Database) -> Result<u64>;
+        fn database_get_unique_terms(db: &Database, id: u32) -> Result<u32>;
+        fn database_get_uuid(db: &Database) -> Result<UniquePtr<CxxString>>;
+        fn database_get_value_freq(db: &Database, slot: u32) -> Result<u32>;
+        fn database_get_value_lower_bound(db: &Database, slot: u32)
+            -> Result<UniquePtr<CxxString>>;
+        fn database_get_value_upper_bound(db: &Database, slot: u32)
+            -> Result<UniquePtr<CxxString>>;
+        fn database_get_wdf_upper_bound(db: &Database, term: &CxxString) -> Result<u32>;
+        fn database_has_document(db: &Database, id: u32) -> Result<bool>;
+        fn database_has_positions(db: &Database) -> Result<bool>;
+        fn database_metadata_keys_begin(
+            db: &Database,
+            prefix: &CxxString,
+        ) -> Result<UniquePtr<TermIterator>>;
+        fn database_postlist_begin(
+            db: &Database,
+            term: &CxxString,
+        ) -> Result<UniquePtr<PostingIterator>>;
+        fn database_reopen(db: Pin<&mut Database>) -> Result<bool>;
+        fn database_spellings_begin(db: &Database) -> Result<UniquePtr<TermIterator>>;
+        fn database_synonym_keys_begin(
+            db: &Database,
+            prefix: &CxxString,
+        ) -> Result<UniquePtr<TermIterator>>;
+        fn database_synonyms_begin(
+            db: &Database,
+            term: &CxxString,
+        ) -> Result<UniquePtr<TermIterator>>;
+        fn database_term_exists(db: &Database, term: &CxxString) -> Result<bool>;
+        fn database_valuestream_begin(db: &Database, slot: u32)
+            -> Result<UniquePtr<ValueIterator>>;
+
+        fn document_add_boolean_term(doc: Pin<&mut Document>, term: &CxxString) -> Result<()>;
+        fn document_add_posting(
+            doc: Pin<&mut Document>,
+            term: &CxxString,
+            pos: u32,
+            increment: u32,
+        ) -> Result<()>;
+        fn document_add_term(
+            doc: Pin<&mut Document>,
+            term: &CxxString,
+            increment: u32,
+        ) -> Result<()>;
+        fn document_add_value(doc: Pin<&mut Document>, slot: u32, value: &CxxString) -> Result<()>;
+        fn document_get_data(doc: &Document) -> Result<UniquePtr<CxxString>>;
+        fn document_get_value(doc: &Document, slot: u32) -> Result<UniquePtr<CxxString>>;
+        fn document_remove_posting(
+            doc: Pin<&mut Document>,
+            term: &CxxString,
+            pos: u32,
+            decrement: u32,
+        ) -> Result<()>;
+        fn document_remove_postings(
+            doc: Pin<&mut Document>,
+            term: &CxxString,
+            start: u32,
+            end: u32,
+            decrement: u32,
+        ) -> Result<u32>;
+        fn document_remove_term(doc: Pin<&mut Document>, term: &CxxString) -> Result<()>;
+        fn document_remove_value(doc: Pin<&mut Document>, slot: u32) -> Result<()>;
+        fn document_serialise(doc: &Document) -> Result<UniquePtr<CxxString>>;
+        fn document_termlist_begin(doc: &Document) -> Result<UniquePtr<TermIterator>>;
+        fn document_termlist_count(doc: &Document) -> Result<u32>;
+        fn document_unserialise(data: &CxxString) -> Result<UniquePtr<Document>>;
+        fn document_values_begin(doc: &Document) -> Result<UniquePtr<ValueIterator>>;
+        fn document_values_count(doc: &Document) -> Result<u32>;
+
+        unsafe fn enquire_get_eset(
+            e: &Enquire,
+            maxitems: u32,
+            rset: &RSet,
+            flags: i32,
+            decider: *const FfiExpandDecider,
+            min_wt: f64,
+        ) -> Result<UniquePtr<ESet>>;
+        unsafe fn enquire_get_mset(
+            e: &Enquire,
+            first: u32,
+            maxitems: u32,
+            atleast: u32,
+            rset: *const RSet,
+            decider: *const FfiMatchDecider,
+        ) -> Result<UniquePtr<MSet>>;
+
+        fn mset_get_termfreq(mset: &MSet, term: &CxxString) -> Result<u32>;
+        fn mset_snippet(
+            mset: &MSet,
+            text: &CxxString,
+            length: usize,
+            stemmer: &Stem,
+            flags: u32,
+            hl_start: &CxxString,
+            hl_end: &CxxString,
+            omit: &CxxString,
+        ) -> Result<UniquePtr<CxxString>>;
+
+        fn mset_iterator_get_document(it: &MSetIterator) -> Result<UniquePtr<Document>>;
+
+        fn position_iterator_increment(it: Pin<&mut PositionIterator>) -> Result<()>;
+        fn position_iterator_position(it: &PositionIterator) -> Result<u32>;
+
+        fn posting_iterator_docid(it: &PostingIterator) -> Result<u32>;
+        fn posting_iterator_get_doclength(it: &PostingIterator) -> Result<u32>;
+        fn posting_iterator_get_unique_terms(it: &PostingIterator) -> Result<u32>;
+        fn posting_iterator_get_wdf(it: &PostingIterator) -> Result<u32>;
+        fn posting_iterator_positionlist_begin(
+            it: &PostingIterator,
+        ) -> Result<UniquePtr<PositionIterator>>;
+        fn posting_iterator_positionlist_count(it: &PostingIterator) -> Result<u32>;
+        fn posting_iterator_increment(it: Pin<&mut PostingIterator>) -> Result<()>;
+        fn posting_iterator_skip_to(it: Pin<&mut PostingIterator>, id: u32) -> Result<()>;
+
+        unsafe fn query_parser_add_boolean_prefix(
+            qp: Pin<&mut QueryParser>,
+            field: &CxxString,
+            prefix: &CxxString,
+            grouping: *const CxxString,
+        ) -> Result<()>;
+        unsafe fn query_parser_add_custom_boolean_prefix(
+            qp: Pin<&mut QueryParser>,
+            field: &CxxString,
+            proc: *mut FfiFieldProcessor,
+            grouping: *const CxxString,
+        ) -> Result<()>;
+        unsafe fn query_parser_add_custom_prefix(
+            qp: Pin<&mut QueryParser>,
+            field: &CxxString,
+            proc: *mut FfiFieldProcessor,
+        ) -> Result<()>;
+        fn query_parser_add_prefix(
+            qp: Pin<&mut QueryParser>,
+            field: &CxxString,
+            prefix: &CxxString,
+        ) -> Result<()>;
+        fn query_parser_parse_query(
+            qp: Pin<&mut QueryParser>,
+            query: &CxxString,
+            flags: u32,
+            default_prefix: &CxxString,
+        ) -> Result<UniquePtr<Query>>;
+
+        fn stem_for_language(lang: &CxxString) -> Result<UniquePtr<Stem>>;
+
+        fn term_generator_index_text(
+            tg: Pin<&mut TermGenerator>,
+            text: &CxxString,
+            increment: u32,
+            prefix: &CxxString,
+        ) -> Result<()>;
+
+        fn term_iterator_get_termfreq(it: &TermIterator) -> Result<u32>;
+        fn term_iterator_get_wdf(it: &TermIterator) -> Result<u32>;
+        fn term_iterator_positionlist_begin(
+            it: &TermIterator,
+        ) -> Result<UniquePtr<PositionIterator>>;
+        fn term_iterator_increment(it: Pin<&mut TermIterator>) -> Result<()>;
+        fn term_iterator_positionlist_count(it: &TermIterator) -> Result<u32>;
+        fn term_iterator_skip_to(it: Pin<&mut TermIterator>, term: &CxxString) -> Result<()>;
+        fn term_iterator_term(it: &TermIterator) -> Result<UniquePtr<CxxString>>;
+
+        fn value_iterator_increment(it: Pin<&mut ValueIterator>) -> Result<()>;
+        fn value_iterator_skip_to(it: Pin<&mut ValueIterator>, id: u32) -> Result<()>;
+        fn value_iterator_value(it: &ValueIterator) -> Result<UniquePtr<CxxString>>;
+
+        fn writable_database_open(
+            path: &CxxString,
+            flags: i32,
+            block_size: i32,
+        ) -> Result<UniquePtr<WritableDatabase>>;
+        fn writable_database_add_database(
+            db: Pin<&mut WritableDatabase>,
+            other: &WritableDatabase,
+        ) -> Result<()>;
+        fn writable_database_add_document(
+            db: Pin<&mut WritableDatabase>,
+            doc: &Document,
+        ) -> Result<u32>;
+        fn writable_database_add_spelling(
+            db: &WritableDatabase,
+            word: &CxxString,
+            increment: u32,
+        ) -> Result<()>;
+        fn writable_database_add_synonym(
+            db: &WritableDatabase,
+            term: &CxxString,
+            synonym: &CxxString,
+        ) -> Result<()>;
+        fn writable_database_begin_transaction(
+            db: Pin<&mut WritableDatabase>,
+            flushed: bool,
+        ) -> Result<()>;
+        fn writable_database_cancel_transaction(db: Pin<&mut WritableDatabase>) -> Result<()>;
+        fn writable_database_commit(db: Pin<&mut WritableDatabase>) -> Result<()>;
+        fn writable_database_commit_transaction(db: Pin<&mut WritableDatabase>) -> Result<()>;
+        fn writable_database_delete_document(db: Pin<&mut WritableDatabase>, id: u32)
+            -> Result<()>;
+        fn writable_database_delete_document_by_term(
+            db: Pin<&mut WritableDatabase>,
+            term: &CxxString,
+        ) -> Result<()>;
+        fn writable_database_remove_spelling(
+            db: &WritableDatabase,
+            word: &CxxString,
+            decrement: u32,
+        ) -> Result<()>;
+        fn writable_database_remove_synonym(
+            db: &WritableDatabase,
+            term: &CxxString,
+            synonym: &CxxString,
+        ) -> Result<()>;
+        fn writable_database_replace_document(
+            db: Pin<&mut WritableDatabase>,
+            id: u32,
+            doc: &Document,
+        ) -> Result<()>;
+        fn writable_database_replace_document_by_term(
+            db: Pin<&mut WritableDatabase>,
+            term: &CxxString,
+            doc: &Document,
+        ) -> Result<u32>;
+        fn writable_database_set_metadata(
+            db: Pin<&mut WritableDatabase>,
+            key: &CxxString,
+            value: &CxxString,
+        ) -> Result<()>;
+    }
+}
+
+#[subclass]
+pub struct RustCompactor {
+    progress: Option<Box<dyn FnMut(&str, &str) + 'static>>,
+    resolve: Option<Box<dyn FnMut(&str, &[Bytes]) -> Bytes + 'static>>,
+}
+
+impl RustCompactor {
+    pub fn from_closures(
+        progress: Option<Box<dyn FnMut(&str, &str) + 'static>>,
+        resolve: Option<Box<dyn FnMut(&str, &[Bytes]) -> Bytes + 'static>>,
+    ) -> Rc<RefCell<Self>> {
+        let me = Self {
+            progress,
+            resolve,
+            cpp_peer: Default::default(),
+        };
+        Self::new_rust_owned(me)
+    }
+}
+
+impl shim::FfiCompactor_methods for RustCompactor {
+    fn report_status(&mut self, table: &CxxString, status: &CxxString) {
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&table.to_string(), &status.to_string())
+        }
+    }
+
+    fn resolve_metadata(
+        &mut self,
+        key: &CxxString,
+        tags: &CxxVector<CxxString>,
+    ) -> UniquePtr<CxxString> {
+        let tags = tags.iter().map(cxx_bytes).collect::<Vec<_>>();
+        match self.resolve.as_mut() {
+            Some(resolve) => resolve(&key.to_string(), &tags),
+            // Match the upstream default of keeping the first value
+            None => tags.into_iter().next().unwrap_or_default(),
+        }
+        .to_cxx_string()
+    }
+}
+
 #[subclass]
 pub struct RustExpandDecider {
     inner: Pin<Box<dyn crate::ExpandDecider + 'static>>,
@@ -354,6 +702,13 @@ impl PartialEq for TermIterator {
     }
 }
 
+/// Compare two instances of `ValueIterator`
+impl PartialEq for ValueIterator {
+    fn eq(&self, other: &Self) -> bool {
+        shim::value_iterator_eq(self, other)
+    }
+}
+
 mod private {
     pub trait Sealed {}
 
@@ -1,8 +1,9 @@
-use crate::ffi;
+use crate::ffi::{self, fallible};
 
-use std::pin::Pin;
+use std::{marker::PhantomData, pin::Pin, rc::Rc};
 
 use autocxx::prelude::*;
+use bytes::Bytes;
 
 #[derive(Clone)]
 pub struct ESetIter<'eset> {
@@ -86,7 +87,7 @@ impl Iterator for MSetIter<'_> {
         match &mut self.cursor_fwd {
             x if x == &self.cursor_rev || x == &self.mset.end() => None,
             c => {
-                let item = crate::Match::new(c.clone());
+                let item = crate::Match::new(c.clone(), self.mset.shards());
                 ffi::shim::mset_iterator_increment(c.as_mut());
                 self.size.1 += 1;
                 Some(item)
@@ -104,28 +105,35 @@ impl DoubleEndedIterator for MSetIter<'_> {
             c => {
                 ffi::shim::mset_iterator_decrement(c.as_mut());
                 self.size.1 += 1;
-                Some(crate::Match::new(c.clone()))
+                Some(crate::Match::new(c.clone(), self.mset.shards()))
             }
         }
     }
 }
 
+/// An iterator over the positions at which a term occurs in a document
+///
+/// Positions are read up front, since the iterator which produced them may move on before they
+/// are consumed
 #[derive(Clone)]
-pub struct PositionIter {
-    size: (u32, u32),
-    cursor: Pin<Box<ffi::PositionIterator>>,
-    end: Pin<Box<ffi::PositionIterator>>,
-}
+pub struct PositionIter(std::vec::IntoIter<u32>);
 
 impl PositionIter {
-    pub(crate) fn new(term: &crate::Term) -> Self {
-        let size = (term.positions_len(), 0);
-        let term_iter = AsRef::<ffi::TermIterator>::as_ref(term);
-        Self {
-            size,
-            cursor: term_iter.positionlist_begin().within_box(),
-            end: term_iter.positionlist_end().within_box(),
+    pub(crate) fn new(positions: Vec<u32>) -> Self {
+        Self(positions.into_iter())
+    }
+
+    /// Read every position from `cursor` up to `end`
+    pub(crate) fn read(
+        mut cursor: Pin<Box<ffi::PositionIterator>>,
+        end: Pin<Box<ffi::PositionIterator>>,
+    ) -> crate::Result<Vec<u32>> {
+        let mut positions = Vec::new();
+        while cursor != end {
+            positions.push(fallible::position_iterator_position(&cursor)?);
+            fallible::position_iterator_increment(cursor.as_mut())?;
         }
+        Ok(positions)
     }
 }
 
@@ -133,21 +141,75 @@ impl Iterator for PositionIter {
     type Item = crate::Position;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.cursor == self.end {
-            false => {
-                let item = ffi::shim::position_iterator_position(&self.cursor);
-                ffi::shim::position_iterator_increment(self.cursor.as_mut());
-                self.size.1 += 1;
-                Some(item.into())
-            }
-            true => None,
-        }
+        self.0.next().map(crate::Position::from)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (total, offset) = self.size;
-        let remaining = (total - offset).try_into().unwrap();
-        (remaining, Some(remaining))
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for PositionIter {}
+
+pub struct PostingIter {
+    cursor: Pin<Box<ffi::PostingIterator>>,
+    end: Pin<Box<ffi::PostingIterator>>,
+    stats: crate::PostingStats,
+    positional: bool,
+    steps: Steps,
+}
+
+impl PostingIter {
+    /// Create an iterator over a posting list, which has positional information unless it is the
+    /// all-documents posting list
+    pub(crate) fn new(
+        start: Pin<Box<ffi::PostingIterator>>,
+        end: Pin<Box<ffi::PostingIterator>>,
+        positional: bool,
+    ) -> Self {
+        Self {
+            cursor: start,
+            end,
+            stats: crate::PostingStats::empty(),
+            positional,
+            steps: Steps::default(),
+        }
+    }
+
+    /// Read the given statistics for each [`Posting`][crate::Posting], as well as its
+    /// [`DocId`][crate::DocId]
+    ///
+    /// The statistics must be read before the iterator moves on, so any which are needed have to
+    /// be requested up front
+    pub fn with_stats(mut self, stats: crate::PostingStats) -> Self {
+        self.stats |= stats;
+        self
+    }
+
+    /// Advance to the first posting whose [`DocId`][crate::DocId] is at least `id`
+    ///
+    /// The next call to `next()` will yield that posting, if any
+    pub fn skip_to(&mut self, id: impl Into<crate::DocId>) -> crate::Result<()> {
+        let id: crate::DocId = id.into();
+        if !ffi::shim::posting_iterator_eq(&self.cursor, &self.end) {
+            fallible::posting_iterator_skip_to(self.cursor.as_mut(), id.into())?;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for PostingIter {
+    type Item = crate::Result<crate::Posting>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Xapian shares state between copies of a PostingIterator, so everything must be read
+        // before the cursor is incremented
+        self.steps.step(
+            &mut self.cursor,
+            |cursor| ffi::shim::posting_iterator_eq(cursor, &self.end),
+            |cursor| crate::Posting::new(cursor, self.stats, self.positional),
+            |cursor| fallible::posting_iterator_increment(cursor.as_mut()),
+        )
     }
 }
 
@@ -187,18 +249,57 @@ impl Iterator for SubqueryIter<'_> {
 
 impl ExactSizeIterator for SubqueryIter<'_> {}
 
-#[derive(Clone)]
+/// The statistics which are read for each term yielded by a [`TermIter`]
+///
+/// Copies of a Xapian `TermIterator` share their position, so anything a [`Term`][crate::Term]
+/// reports must be read before the iterator which produced it moves on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TermStats {
+    /// No per-term statistics, as for metadata keys, synonyms and query terms
+    None,
+    /// The term frequency, as for the terms of a database or its spelling dictionary
+    Frequency,
+    /// The within-document frequency and positions of a document's terms, along with the term
+    /// frequency if the document was read from a database
+    Document { frequency: bool },
+}
+
 pub struct TermIter {
     cursor: Pin<Box<ffi::TermIterator>>,
     end: Pin<Box<ffi::TermIterator>>,
+    stats: TermStats,
+    db: Option<Rc<Pin<Box<ffi::Database>>>>,
+    steps: Steps,
 }
 
 impl TermIter {
     pub(crate) fn new(
         start: Pin<Box<ffi::TermIterator>>,
         end: Pin<Box<ffi::TermIterator>>,
+        stats: TermStats,
     ) -> Self {
-        Self { cursor: start, end }
+        Self {
+            cursor: start,
+            end,
+            stats,
+            db: None,
+            steps: Steps::default(),
+        }
+    }
+
+    /// Create an iterator whose terms can look up database-wide statistics in `db`
+    pub(crate) fn with_database(
+        start: Pin<Box<ffi::TermIterator>>,
+        end: Pin<Box<ffi::TermIterator>>,
+        db: Pin<Box<ffi::Database>>,
+    ) -> Self {
+        Self {
+            cursor: start,
+            end,
+            stats: TermStats::Frequency,
+            db: Some(Rc::new(db)),
+            steps: Steps::default(),
+        }
     }
 }
 
@@ -209,16 +310,194 @@ impl AsRef<ffi::TermIterator> for TermIter {
 }
 
 impl Iterator for TermIter {
-    type Item = crate::Term;
+    type Item = crate::Result<crate::Term>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.cursor == self.end {
-            false => {
-                let term = crate::Term::new(self.cursor.clone());
-                ffi::shim::term_iterator_increment(self.cursor.as_mut());
-                Some(term)
-            }
-            true => None,
+        // The term shares its cursor with this iterator, so it must be read before advancing
+        self.steps.step(
+            &mut self.cursor,
+            |cursor| cursor == &self.end,
+            |cursor| crate::Term::new(cursor.clone(), self.stats, self.db.clone()),
+            |cursor| fallible::term_iterator_increment(cursor.as_mut()),
+        )
+    }
+}
+
+pub struct ValueIter {
+    cursor: Pin<Box<ffi::ValueIterator>>,
+    end: Pin<Box<ffi::ValueIterator>>,
+    steps: Steps,
+}
+
+impl ValueIter {
+    pub(crate) fn new(
+        start: Pin<Box<ffi::ValueIterator>>,
+        end: Pin<Box<ffi::ValueIterator>>,
+    ) -> Self {
+        Self {
+            cursor: start,
+            end,
+            steps: Steps::default(),
+        }
+    }
+
+    /// Advance to the first value whose [`DocId`][crate::DocId] is at least `id`
+    ///
+    /// The next call to `next()` will yield that value, if any
+    pub fn skip_to(&mut self, id: impl Into<crate::DocId>) -> crate::Result<()> {
+        let id: crate::DocId = id.into();
+        if self.cursor != self.end {
+            fallible::value_iterator_skip_to(self.cursor.as_mut(), id.into())?;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for ValueIter {
+    type Item = crate::Result<(crate::DocId, Bytes)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.steps.step(
+            &mut self.cursor,
+            |cursor| cursor == &self.end,
+            |cursor| {
+                let id = ffi::shim::value_iterator_docid(cursor);
+                let value = fallible::value_iterator_value(cursor)?;
+                Ok((
+                    unsafe { crate::DocId::new_unchecked(id) },
+                    ffi::cxx_bytes(&value),
+                ))
+            },
+            |cursor| fallible::value_iterator_increment(cursor.as_mut()),
+        )
+    }
+}
+
+/// An iterator over the values stored in a [`Document`][crate::Document], in slot order
+pub struct DocumentValueIter {
+    cursor: Pin<Box<ffi::ValueIterator>>,
+    end: Pin<Box<ffi::ValueIterator>>,
+    steps: Steps,
+}
+
+impl DocumentValueIter {
+    pub(crate) fn new(
+        start: Pin<Box<ffi::ValueIterator>>,
+        end: Pin<Box<ffi::ValueIterator>>,
+    ) -> Self {
+        Self {
+            cursor: start,
+            end,
+            steps: Steps::default(),
+        }
+    }
+}
+
+impl Iterator for DocumentValueIter {
+    type Item = crate::Result<(crate::Slot, Bytes)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.steps.step(
+            &mut self.cursor,
+            |cursor| cursor == &self.end,
+            |cursor| {
+                let slot = ffi::shim::value_iterator_valueno(cursor);
+                let value = fallible::value_iterator_value(cursor)?;
+                Ok((crate::Slot(slot), ffi::cxx_bytes(&value)))
+            },
+            |cursor| fallible::value_iterator_increment(cursor.as_mut()),
+        )
+    }
+}
+
+/// A [`DocumentValueIter`] which deserializes each value via [`FromValue`][crate::FromValue]
+pub struct TypedDocumentValueIter<T> {
+    inner: DocumentValueIter,
+    _marker: PhantomData<T>,
+}
+
+impl<T: crate::FromValue> TypedDocumentValueIter<T> {
+    pub(crate) fn new(inner: DocumentValueIter) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: crate::FromValue> Iterator for TypedDocumentValueIter<T> {
+    type Item = crate::Result<(crate::Slot, Result<T, T::Error>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|item| item.map(|(slot, value)| (slot, T::deserialize(value))))
+    }
+}
+
+/// A [`ValueIter`] which deserializes each value via [`FromValue`][crate::FromValue]
+pub struct TypedValueIter<T> {
+    inner: ValueIter,
+    _marker: PhantomData<T>,
+}
+
+impl<T: crate::FromValue> TypedValueIter<T> {
+    pub(crate) fn new(inner: ValueIter) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Advance to the first value whose [`DocId`][crate::DocId] is at least `id`
+    ///
+    /// The next call to `next()` will yield that value, if any
+    pub fn skip_to(&mut self, id: impl Into<crate::DocId>) -> crate::Result<()> {
+        self.inner.skip_to(id)
+    }
+}
+
+impl<T: crate::FromValue> Iterator for TypedValueIter<T> {
+    type Item = crate::Result<(crate::DocId, Result<T, T::Error>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|item| item.map(|(id, value)| (id, T::deserialize(value))))
+    }
+}
+
+/// Steps through a Xapian iterator, reading each item before advancing past it
+///
+/// Either step may throw, such as when the database is modified or closed part way through, in
+/// which case the error is yielded in place of the next item and iteration ends
+#[derive(Default)]
+struct Steps {
+    pending: Option<crate::Error>,
+    failed: bool,
+}
+
+impl Steps {
+    fn step<C, T>(
+        &mut self,
+        cursor: &mut C,
+        at_end: impl FnOnce(&C) -> bool,
+        read: impl FnOnce(&C) -> crate::Result<T>,
+        advance: impl FnOnce(&mut C) -> Result<(), cxx::Exception>,
+    ) -> Option<crate::Result<T>> {
+        if let Some(err) = self.pending.take() {
+            return Some(Err(err));
+        }
+        if self.failed || at_end(cursor) {
+            return None;
+        }
+
+        let item = read(cursor);
+        if item.is_ok() {
+            // An item which was read successfully is still yielded if advancing past it fails
+            self.pending = advance(cursor).err().map(Into::into);
         }
+        self.failed = item.is_err() || self.pending.is_some();
+        Some(item)
     }
 }
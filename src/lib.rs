@@ -1,35 +1,82 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
+mod bulk;
+pub use bulk::{BulkIndexer, BulkStats};
+
+mod check;
+pub use check::{CheckFlags, CheckReport, TableReport};
+
+mod compact;
+pub use compact::{CompactOptions, CompactionLevel};
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use datetime::{Epoch, TimestampOutOfRange};
+
 mod db;
-pub use db::{Database, DbAction, DbBackend, DbFlags, WritableDatabase};
+pub use db::{
+    Database, DbAction, DbBackend, DbFlags, Posting, PostingStats, Shards, WritableDatabase,
+};
 
 mod doc;
 pub use doc::Document;
 
+#[cfg(feature = "serde")]
+mod dump;
+#[cfg(feature = "serde")]
+pub use dump::{Blob, DumpDocument, DumpRecord, DumpTerm};
+
+mod error;
+pub use error::{Error, ErrorInfo, Result};
+
 pub(crate) mod ffi;
 
 mod iter;
+mod mapping;
+#[doc(hidden)]
+pub use mapping::__private;
+pub use mapping::{FromDocument, IntoDocument};
+
+#[cfg(feature = "derive")]
+pub use xapian_rs_derive::XapianDocument;
+
+mod open;
+pub use open::{OpenOptions, ReadOnly, Writable};
+
 mod range;
-pub use range::{NumberRangeProcessor, RangeProcessor, RangeProcessorFlags, StringRangeProcessor};
+pub use range::{
+    IntegerRangeProcessor, NumberRangeProcessor, RangeProcessor, RangeProcessorFlags,
+    StringRangeProcessor, UnsignedRangeProcessor,
+};
 
 #[cfg(feature = "chrono")]
-pub use range::{DateRangeProcessor, DateTimeRangeProcessor};
+pub use range::{
+    DateRangeProcessor, DateTimeRangeProcessor, EpochDateRangeProcessor,
+    EpochDateTimeRangeProcessor,
+};
 
 mod query;
-pub use query::{FieldProcessor, Operator, Query, QueryParser};
+pub use query::{FieldProcessor, Operator, Query, QueryParser, QueryParserFlags};
+
+mod schema;
+pub use schema::{Field, Schema, ValueType};
 
 mod search;
 pub use search::{ESet, Enquire, ExpandDecider, MSet, Match, MatchDecider, MatchSpy, RSet};
 
 mod term;
-pub use term::{Expansion, Stem, StemStrategy, Stopper, Term, TermGenerator};
+pub use term::{boolean_term, Expansion, Stem, StemStrategy, Stopper, Term, TermGenerator};
+
+mod transaction;
+pub use transaction::Transaction;
 
 use std::num::NonZeroU32;
 
 use bytes::Bytes;
 
 /// A newtype wrapper representing a valid (non-zero) Xapian document ID
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DocId(NonZeroU32);
 
 impl DocId {
@@ -98,20 +145,6 @@ pub trait ToValue: Clone {
     fn serialize(&self) -> Bytes;
 }
 
-#[cfg(feature = "chrono")]
-impl ToValue for chrono::NaiveDate {
-    fn serialize(&self) -> Bytes {
-        self.format("%Y%m%d").to_string().into()
-    }
-}
-
-#[cfg(feature = "chrono")]
-impl ToValue for chrono::NaiveDateTime {
-    fn serialize(&self) -> Bytes {
-        self.format("%Y%m%d%H%M%S").to_string().into()
-    }
-}
-
 impl ToValue for f64 {
     fn serialize(&self) -> Bytes {
         let value = ffi::sortable_serialise(*self);
@@ -133,13 +166,60 @@ primitive_to_value!(f32);
 primitive_to_value!(i8);
 primitive_to_value!(i16);
 primitive_to_value!(i32);
-primitive_to_value!(i64);
-primitive_to_value!(isize);
 primitive_to_value!(u8);
 primitive_to_value!(u16);
 primitive_to_value!(u32);
-primitive_to_value!(u64);
-primitive_to_value!(usize);
+
+// 64-bit integers can't be represented exactly by an `f64`, so are stored as 8 big-endian bytes
+// instead, with the sign bit of signed integers flipped so that negative values sort first
+const SIGN_BIT: u64 = 1 << 63;
+
+impl ToValue for i64 {
+    fn serialize(&self) -> Bytes {
+        Bytes::copy_from_slice(&(*self as u64 ^ SIGN_BIT).to_be_bytes())
+    }
+}
+
+impl ToValue for isize {
+    fn serialize(&self) -> Bytes {
+        (*self as i64).serialize()
+    }
+}
+
+impl ToValue for u64 {
+    fn serialize(&self) -> Bytes {
+        Bytes::copy_from_slice(&self.to_be_bytes())
+    }
+}
+
+impl ToValue for usize {
+    fn serialize(&self) -> Bytes {
+        (*self as u64).serialize()
+    }
+}
+
+impl ToValue for bool {
+    fn serialize(&self) -> Bytes {
+        Bytes::from_static(if *self { b"1" } else { b"0" })
+    }
+}
+
+impl ToValue for char {
+    fn serialize(&self) -> Bytes {
+        Bytes::copy_from_slice(self.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+}
+
+/// `None` is stored as an empty value, which Xapian treats as no value at all
+///
+/// `Some` values are stored as-is, so a `Some` whose value serializes to nothing, such as
+/// `Some(String::new())`, `Some(Bytes::new())` or `Some(None)`, is indistinguishable from `None`
+/// and will be read back as `None`
+impl<T: ToValue> ToValue for Option<T> {
+    fn serialize(&self) -> Bytes {
+        self.as_ref().map_or_else(Bytes::new, ToValue::serialize)
+    }
+}
 
 impl ToValue for Bytes {
     fn serialize(&self) -> Bytes {
@@ -171,6 +251,83 @@ impl ToValue for &String {
     }
 }
 
+#[cfg(feature = "uuid")]
+impl ToValue for uuid::Uuid {
+    fn serialize(&self) -> Bytes {
+        Bytes::copy_from_slice(self.as_bytes())
+    }
+}
+
+// Composite values use the same encoding as Xapian's MultiValueKeyMaker: each component but the
+// last has any zero bytes escaped as `\0\xff` and is terminated by `\0\0`, so that the composite
+// sorts in the same order as its components
+fn push_component(key: &mut Vec<u8>, component: &[u8]) {
+    for byte in component {
+        key.push(*byte);
+        if *byte == 0 {
+            key.push(0xff);
+        }
+    }
+    key.extend_from_slice(&[0, 0]);
+}
+
+fn pop_component(rest: &mut &[u8]) -> Result<Bytes, InvalidValue> {
+    let mut component = Vec::new();
+    let mut i = 0;
+    while i < rest.len() {
+        match (rest[i], rest.get(i + 1)) {
+            (0, Some(0xff)) => component.push(0),
+            (0, Some(0)) => {
+                *rest = &rest[i + 2..];
+                return Ok(component.into());
+            }
+            (0, _) => return Err(InvalidValue::new("invalid escape in composite value")),
+            (byte, _) => {
+                component.push(byte);
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    Err(InvalidValue::new("too few components in composite value"))
+}
+
+/// Tuples are stored as a composite of their components, which sorts by the first component, then
+/// by the second, and so on, so long as each component's own encoding sorts correctly
+macro_rules! tuple_value {
+    ($($t:ident $v:ident),+; $last_t:ident $last_v:ident) => {
+        impl<$($t: ToValue,)+ $last_t: ToValue> ToValue for ($($t,)+ $last_t) {
+            fn serialize(&self) -> Bytes {
+                let ($($v,)+ $last_v) = self;
+                let mut key = Vec::new();
+                $(push_component(&mut key, &$v.serialize());)+
+                key.extend_from_slice(&$last_v.serialize());
+                key.into()
+            }
+        }
+
+        impl<$($t: FromValue,)+ $last_t: FromValue> FromValue for ($($t,)+ $last_t) {
+            type Error = InvalidValue;
+
+            fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+                let mut rest = value.as_ref();
+                Ok((
+                    $($t::deserialize(pop_component(&mut rest)?).map_err(InvalidValue::from_error)?,)+
+                    $last_t::deserialize(Bytes::copy_from_slice(rest))
+                        .map_err(InvalidValue::from_error)?,
+                ))
+            }
+        }
+    };
+}
+
+tuple_value!(A a; B b);
+tuple_value!(A a, B b; C c);
+tuple_value!(A a, B b, C c; D d);
+tuple_value!(A a, B b, C c, D d; E e);
+tuple_value!(A a, B b, C c, D d, E e; F f);
+
 /// A trait representing the ability to be loaded from a Xapian document value.
 pub trait FromValue: Clone + PartialEq + PartialOrd + Sized {
     /// The error type returned if deserialization fails
@@ -205,13 +362,85 @@ primitive_from_value!(f32);
 primitive_from_value!(i8);
 primitive_from_value!(i16);
 primitive_from_value!(i32);
-primitive_from_value!(i64);
-primitive_from_value!(isize);
 primitive_from_value!(u8);
 primitive_from_value!(u16);
 primitive_from_value!(u32);
-primitive_from_value!(u64);
-primitive_from_value!(usize);
+
+impl FromValue for i64 {
+    type Error = InvalidValue;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        Ok((u64::deserialize(value)? ^ SIGN_BIT) as i64)
+    }
+}
+
+impl FromValue for isize {
+    type Error = InvalidValue;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        isize::try_from(i64::deserialize(value)?).map_err(InvalidValue::from_error)
+    }
+}
+
+impl FromValue for u64 {
+    type Error = InvalidValue;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        let bytes = <[u8; 8]>::try_from(value.as_ref()).map_err(|_| {
+            InvalidValue::new(format!(
+                "expected 8 bytes for a 64-bit integer, found {}",
+                value.len()
+            ))
+        })?;
+        Ok(u64::from_be_bytes(bytes))
+    }
+}
+
+impl FromValue for usize {
+    type Error = InvalidValue;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        usize::try_from(u64::deserialize(value)?).map_err(InvalidValue::from_error)
+    }
+}
+
+impl FromValue for bool {
+    type Error = InvalidValue;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        match value.as_ref() {
+            b"0" => Ok(false),
+            b"1" => Ok(true),
+            _ => Err(InvalidValue::new(format!("invalid bool value {value:?}"))),
+        }
+    }
+}
+
+impl FromValue for char {
+    type Error = InvalidValue;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        let s = std::str::from_utf8(&value).map_err(InvalidValue::from_error)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(InvalidValue::new(format!("invalid char value {s:?}"))),
+        }
+    }
+}
+
+/// An empty value is deserialized as `None`, including one written from a `Some` whose value
+/// serializes to nothing
+impl<T: FromValue> FromValue for Option<T> {
+    type Error = T::Error;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        match value.is_empty() {
+            true => Ok(None),
+            false => T::deserialize(value).map(Some),
+        }
+    }
+}
 
 impl FromValue for Bytes {
     type Error = std::convert::Infallible;
@@ -229,6 +458,38 @@ impl FromValue for String {
     }
 }
 
+#[cfg(feature = "uuid")]
+impl FromValue for uuid::Uuid {
+    type Error = uuid::Error;
+
+    fn deserialize(value: Bytes) -> Result<Self, Self::Error> {
+        uuid::Uuid::from_slice(&value)
+    }
+}
+
+/// The error returned when a value can't be deserialized by one of the built-in
+/// [`FromValue`] implementations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidValue(String);
+
+impl InvalidValue {
+    fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+
+    fn from_error(error: impl std::fmt::Display) -> Self {
+        Self(error.to_string())
+    }
+}
+
+impl std::fmt::Display for InvalidValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidValue {}
+
 /// A newtype wrapper representing a valid Xapian slot number (aka `valueno`)
 #[derive(Debug, Clone, Copy)]
 pub struct Slot(ffi::valueno);
@@ -0,0 +1,89 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Conversion of a Rust type into a [`Document`][crate::Document]
+///
+/// This can be derived via `#[derive(XapianDocument)]` when the `derive` feature is enabled
+pub trait IntoDocument {
+    /// Convert `self` into a document, indexing any free text with `indexer`
+    fn into_document(self, indexer: &mut crate::TermGenerator) -> crate::Result<crate::Document>;
+
+    /// The term which uniquely identifies this document, if any
+    ///
+    /// This is suitable for use with
+    /// [`WritableDatabase::replace_document_by_term`][crate::WritableDatabase::replace_document_by_term]
+    /// and [`BulkIndexer::upsert`][crate::BulkIndexer::upsert]
+    fn unique_term(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Conversion of a [`Document`][crate::Document] into a Rust type
+///
+/// This can be derived via `#[derive(XapianDocument)]` when the `derive` feature is enabled
+pub trait FromDocument: Sized {
+    /// Create an instance of this type from `doc`
+    fn from_document(doc: &crate::Document) -> crate::Result<Self>;
+}
+
+/// Helpers used by code generated by `#[derive(XapianDocument)]`
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    fn field_error(field: &str, error: impl Display) -> crate::Error {
+        crate::Error::Serialisation(crate::ErrorInfo::new(format!(
+            "failed to restore field `{field}`: {error}"
+        )))
+    }
+
+    pub fn value<T: crate::FromValue + Default>(
+        doc: &crate::Document,
+        slot: u32,
+        field: &str,
+    ) -> crate::Result<T> {
+        match doc.value::<T>(slot)? {
+            Some(value) => value.map_err(|e| field_error(field, e)),
+            None => Ok(T::default()),
+        }
+    }
+
+    pub fn data<T: crate::FromValue + Default>(
+        doc: &crate::Document,
+        field: &str,
+    ) -> crate::Result<T> {
+        match doc.data()? {
+            data if data.is_empty() => Ok(T::default()),
+            data => T::deserialize(data).map_err(|e| field_error(field, e)),
+        }
+    }
+
+    // The derive requires a non-empty prefix which no other field's prefix starts with, so the
+    // first matching term belongs to this field
+    pub fn term<T: FromStr + Default>(
+        doc: &crate::Document,
+        prefix: &str,
+        field: &str,
+    ) -> crate::Result<T>
+    where
+        T::Err: Display,
+    {
+        // Only the matching term is decoded, as other terms may not be valid UTF-8
+        let value = doc
+            .terms()?
+            .find_map(|term| {
+                term.map(|term| {
+                    crate::term::strip_boolean_prefix(AsRef::<[u8]>::as_ref(&term), prefix)
+                        .map(<[u8]>::to_vec)
+                })
+                .transpose()
+            })
+            .transpose()?;
+        match value {
+            Some(value) => std::str::from_utf8(&value)
+                .map_err(|e| field_error(field, e))?
+                .parse()
+                .map_err(|e| field_error(field, e)),
+            None => Ok(T::default()),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{DbAction, DbBackend, DbFlags, Error};
+
+use std::{marker::PhantomData, path::Path, sync::Mutex};
+
+/// Marks [`OpenOptions`] which open a read-only [`Database`][crate::Database]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadOnly;
+
+/// Marks [`OpenOptions`] which open (or create) a [`WritableDatabase`][crate::WritableDatabase]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Writable;
+
+/// A builder for opening a [`Database`][crate::Database] or
+/// [`WritableDatabase`][crate::WritableDatabase] with a combination of Xapian's `DB_*` flags
+///
+/// Options start out read-only, and [`OpenOptions::writable`] switches to options for a
+/// writable database, which is the only kind that accepts an action, flags or a block size.
+/// Combinations which still conflict are rejected with
+/// [`Error::InvalidArgument`][crate::Error::InvalidArgument] before Xapian is called
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpenOptions<M = ReadOnly> {
+    action: Option<DbAction>,
+    backend: DbBackend,
+    flags: DbFlags,
+    block_size: Option<u32>,
+    flush_threshold: Option<u32>,
+    mode: PhantomData<M>,
+}
+
+impl<M> OpenOptions<M> {
+    /// Set the backend to use, which defaults to [`DbBackend::Auto`]
+    ///
+    /// When opening an existing database, this restricts which backend it may use
+    pub fn backend(mut self, backend: DbBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+}
+
+impl OpenOptions<ReadOnly> {
+    /// Create a new set of options for a read-only database, which use the default backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the read-only [`Database`][crate::Database] at `path`
+    ///
+    /// `path` may refer to a database directory, a single-file database or a stub database file
+    pub fn open(&self, path: impl AsRef<Path>) -> crate::Result<crate::Database> {
+        crate::Database::open_with_flags(path.as_ref(), self.backend as i32)
+    }
+
+    /// Open a read-only single-file [`Database`][crate::Database] from an open file
+    ///
+    /// The database is read starting from the current offset of `file`, so it may be embedded
+    /// in a larger file
+    #[cfg(unix)]
+    pub fn open_file(&self, file: std::fs::File) -> crate::Result<crate::Database> {
+        use std::os::unix::io::{AsRawFd, IntoRawFd};
+
+        if !matches!(self.backend, DbBackend::Auto | DbBackend::Glass) {
+            return Err(Error::invalid_argument(
+                "single-file databases must use the glass backend",
+            ));
+        }
+        let db = crate::Database::open_fd(file.as_raw_fd(), self.backend as i32)?;
+        // The database now owns the descriptor, and closes it when it is closed. Until then,
+        // `file` keeps ownership so that the descriptor is closed if opening fails
+        let _ = file.into_raw_fd();
+        Ok(db)
+    }
+
+    /// Switch to options for opening (or creating) a writable database
+    pub fn writable(self) -> OpenOptions<Writable> {
+        OpenOptions {
+            backend: self.backend,
+            ..OpenOptions::default()
+        }
+    }
+}
+
+impl OpenOptions<Writable> {
+    /// Set how to handle the database already existing (or not), which defaults to
+    /// [`DbAction::CreateOrOpen`]
+    pub fn action(mut self, action: DbAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Set the block size (in bytes) to use when creating a new database
+    ///
+    /// Must be a power of 2 between 2048 and 65536 (the default is 8192)
+    pub fn block_size(mut self, size: u32) -> Self {
+        self.block_size = Some(size);
+        self
+    }
+
+    /// Create a new database, failing if one already exists (`DB_CREATE`)
+    pub fn create(self) -> Self {
+        self.action(DbAction::Create)
+    }
+
+    /// Open the database if it exists, create it otherwise (`DB_CREATE_OR_OPEN`)
+    pub fn create_or_open(self) -> Self {
+        self.action(DbAction::CreateOrOpen)
+    }
+
+    /// Overwrite the database if it exists, create it otherwise (`DB_CREATE_OR_OVERWRITE`)
+    pub fn create_or_overwrite(self) -> Self {
+        self.action(DbAction::CreateOrOverwrite)
+    }
+
+    /// Update the database in-place (`DB_DANGEROUS`)
+    pub fn dangerous(self) -> Self {
+        self.flags(DbFlags::DANGEROUS)
+    }
+
+    /// Add the given [`DbFlags`] to the options
+    pub fn flags(mut self, flags: DbFlags) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    /// Set the number of changes after which Xapian commits automatically, which defaults to
+    /// 10000
+    ///
+    /// Xapian only reads this from the `XAPIAN_FLUSH_THRESHOLD` environment variable when a
+    /// database is opened, so [`OpenOptions::open`] sets the variable while opening and then
+    /// restores it. To avoid modifying the environment, set `XAPIAN_FLUSH_THRESHOLD` before
+    /// spawning any threads instead of using this option
+    ///
+    /// # Safety
+    ///
+    /// While `open` runs with these options, no other thread may read or modify the environment
+    /// (other than by opening a database with a flush threshold, which is serialised), as with
+    /// [`std::env::set_var`] on platforms where it isn't thread-safe
+    pub unsafe fn flush_threshold(mut self, changes: u32) -> Self {
+        self.flush_threshold = Some(changes);
+        self
+    }
+
+    /// Try to ensure changes have hit the disk (`DB_FULL_SYNC`)
+    pub fn full_sync(self) -> Self {
+        self.flags(DbFlags::FULL_SYNC)
+    }
+
+    /// Don't attempt to ensure changes have hit the disk (`DB_NO_SYNC`)
+    pub fn no_sync(self) -> Self {
+        self.flags(DbFlags::NO_SYNC)
+    }
+
+    /// Do not create a termlist table when creating the database (`DB_NO_TERMLIST`)
+    pub fn no_termlist(self) -> Self {
+        self.flags(DbFlags::NO_TERMLIST)
+    }
+
+    /// Open an existing database, failing if it does not exist (`DB_OPEN`)
+    pub fn open_existing(self) -> Self {
+        self.action(DbAction::Open)
+    }
+
+    /// If the database is already locked by another writer, wait for the lock (`DB_RETRY_LOCK`)
+    pub fn retry_lock(self) -> Self {
+        self.flags(DbFlags::RETRY_LOCK)
+    }
+
+    /// Open (or create) the [`WritableDatabase`][crate::WritableDatabase] at `path`
+    pub fn open(&self, path: impl AsRef<Path>) -> crate::Result<crate::WritableDatabase> {
+        self.validate()?;
+        let flags =
+            self.action.unwrap_or_default() as i32 | self.backend as i32 | self.flags.bits() as i32;
+        let block_size = self.block_size.unwrap_or(0) as i32;
+        let open = || crate::WritableDatabase::open_with_flags(path.as_ref(), flags, block_size);
+        match self.flush_threshold {
+            Some(changes) => with_flush_threshold(changes, open),
+            None => open(),
+        }
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        if self.flags.contains(DbFlags::NO_SYNC | DbFlags::FULL_SYNC) {
+            return Err(Error::invalid_argument(
+                "NO_SYNC and FULL_SYNC cannot be combined",
+            ));
+        }
+        if self.flags.contains(DbFlags::NO_TERMLIST) && self.action == Some(DbAction::Open) {
+            return Err(Error::invalid_argument(
+                "NO_TERMLIST only applies when creating a database, not opening an existing one",
+            ));
+        }
+        if self.flush_threshold == Some(0) {
+            return Err(Error::invalid_argument(
+                "flush threshold must be at least 1",
+            ));
+        }
+        if let Some(size) = self.block_size {
+            if !(2048..=65536).contains(&size) || !size.is_power_of_two() {
+                return Err(Error::invalid_argument(format!(
+                    "block size must be a power of 2 between 2048 and 65536, not {size}"
+                )));
+            }
+            if self.backend == DbBackend::InMemory {
+                return Err(Error::invalid_argument(
+                    "block size does not apply to in-memory databases",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run `open` with `XAPIAN_FLUSH_THRESHOLD` set to `changes`, restoring its previous value after
+///
+/// Callers must uphold the safety contract of [`OpenOptions::flush_threshold`]
+fn with_flush_threshold<T>(changes: u32, open: impl FnOnce() -> T) -> T {
+    const VAR: &str = "XAPIAN_FLUSH_THRESHOLD";
+    // Serialise databases being opened with a flush threshold, so they can't see each other's
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = std::env::var_os(VAR);
+    std::env::set_var(VAR, changes.to_string());
+    let result = open();
+    match previous {
+        Some(value) => std::env::set_var(VAR, value),
+        None => std::env::remove_var(VAR),
+    }
+    result
+}
@@ -1,4 +1,4 @@
-use crate::ffi::{self, ToCxxString};
+use crate::ffi::{self, fallible, ToCxxString};
 
 use std::{
     cell::RefCell,
@@ -9,6 +9,7 @@ use std::{
 };
 
 use autocxx::{cxx, prelude::*};
+use bitflags::bitflags;
 
 /// A [`FieldProcessor`] can be used to customize the handling of query fields
 pub trait FieldProcessor {
@@ -17,8 +18,8 @@ pub trait FieldProcessor {
 }
 
 trait FfiFieldProcessor: FieldProcessor + Sized + 'static {
-    fn into_ffi(self) -> &'static mut FieldProcessorObj {
-        Box::leak(Box::new(FieldProcessorObj::from(self)))
+    fn into_ffi(self) -> FieldProcessorObj {
+        FieldProcessorObj::from(self)
     }
 }
 
@@ -302,6 +303,7 @@ impl Query {
         crate::iter::TermIter::new(
             self.0.get_terms_begin().within_box(),
             self.0.get_terms_end().within_box(),
+            crate::iter::TermStats::None,
         )
     }
 
@@ -314,6 +316,7 @@ impl Query {
         crate::iter::TermIter::new(
             self.0.get_unique_terms_begin().within_box(),
             self.0.get_unique_terms_end().within_box(),
+            crate::iter::TermStats::None,
         )
     }
 }
@@ -365,18 +368,76 @@ impl Display for Query {
     }
 }
 
+bitflags! {
+    /// Flags to enable or disable the features of the query syntax understood by [`QueryParser`]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct QueryParserFlags: u32 {
+        /// Support boolean operators (`AND`, `OR`, etc) and bracketed subexpressions
+        const BOOLEAN = 0x01;
+        /// Support quoted phrases
+        const PHRASE = 0x02;
+        /// Support `+` and `-`
+        const LOVEHATE = 0x04;
+        /// Support boolean operators in any case
+        const BOOLEAN_ANY_CASE = 0x08;
+        /// Support trailing wildcards, such as `xap*`
+        const WILDCARD = 0x10;
+        /// Allow queries such as `NOT apples`
+        const PURE_NOT = 0x20;
+        /// Treat the final word as a partially entered term
+        const PARTIAL = 0x40;
+        /// Suggest spelling corrections, via [`QueryParser::corrected_query_string`]
+        const SPELLING_CORRECTION = 0x80;
+        /// Support explicit synonym expansion via `~`
+        const SYNONYM = 0x100;
+        /// Automatically expand single terms with their synonyms
+        const AUTO_SYNONYMS = 0x200;
+        /// Automatically expand terms and multi-word phrases with their synonyms
+        const AUTO_MULTIWORD_SYNONYMS = 0x400 | Self::AUTO_SYNONYMS.bits();
+        /// Generate n-grams for CJK text
+        const CJK_NGRAM = 0x800;
+        /// Accumulate the weights of repeated terms
+        const ACCUMULATE = 0x10000;
+        /// Generate terms without positional information
+        const NO_POSITIONS = 0x20000;
+        /// The default set of flags
+        const DEFAULT = Self::PHRASE.bits() | Self::BOOLEAN.bits() | Self::LOVEHATE.bits();
+    }
+}
+
+impl Default for QueryParserFlags {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// A type for building [`Query`] objects from strings
-pub struct QueryParser(Pin<Box<ffi::QueryParser>>);
+pub struct QueryParser {
+    // Xapian only borrows the stopper and processors, so they must outlive (and therefore be
+    // dropped after) the underlying QueryParser
+    ptr: Pin<Box<ffi::QueryParser>>,
+    stopper: Option<crate::term::StopperObj>,
+    field_procs: Vec<FieldProcessorObj>,
+    range_procs: Vec<crate::range::RangeProcessorObj>,
+}
 
 impl QueryParser {
     /// Add a free-text field term prefix
-    pub fn add_prefix<T>(&mut self, field: impl AsRef<str>, prefix: impl Into<Option<T>>)
+    pub fn add_prefix<T>(
+        &mut self,
+        field: impl AsRef<str>,
+        prefix: impl Into<Option<T>>,
+    ) -> crate::Result<()>
     where
         T: AsRef<str> + Default,
     {
         cxx::let_cxx_string!(field = field.as_ref());
         cxx::let_cxx_string!(prefix = prefix.into().unwrap_or_default().as_ref());
-        self.0.as_mut().add_prefix(&field, &prefix)
+        Ok(fallible::query_parser_add_prefix(
+            self.ptr.as_mut(),
+            &field,
+            &prefix,
+        )?)
     }
 
     /// Add a free-text field term prefix backed by a custom [`FieldProcessor`][crate::FieldProcessor]
@@ -384,10 +445,14 @@ impl QueryParser {
         &mut self,
         field: impl AsRef<str>,
         field_proc: T,
-    ) {
+    ) -> crate::Result<()> {
         cxx::let_cxx_string!(field = field.as_ref());
-        let field_proc = field_proc.clone().into_ffi();
-        unsafe { ffi::shim::query_parser_add_prefix(self.0.as_mut(), &field, field_proc.upcast()) }
+        let mut field_proc = field_proc.clone().into_ffi();
+        unsafe {
+            fallible::query_parser_add_custom_prefix(self.ptr.as_mut(), &field, field_proc.upcast())
+        }?;
+        self.field_procs.push(field_proc);
+        Ok(())
     }
 
     /// Register a [`FieldProcessor`][crate::FieldProcessor] for a boolean prefix
@@ -396,23 +461,27 @@ impl QueryParser {
         field: impl AsRef<str>,
         field_proc: T,
         grouping: impl Into<Option<U>>,
-    ) where
+    ) -> crate::Result<()>
+    where
         T: crate::FieldProcessor + Clone + 'static,
         U: AsRef<str>,
     {
         cxx::let_cxx_string!(field = field.as_ref());
-        let grouping = grouping
-            .into()
-            .map_or(std::ptr::null(), |g| g.as_ref().to_cxx_string().into_raw());
-        let field_proc = field_proc.clone().into_ffi();
+        let grouping = grouping.into().map(|g| g.as_ref().to_cxx_string());
+        let grouping_ptr = grouping
+            .as_ref()
+            .map_or(std::ptr::null(), |g| &**g as *const _);
+        let mut field_proc = field_proc.clone().into_ffi();
         unsafe {
-            ffi::shim::query_parser_add_boolean_prefix(
-                self.0.as_mut(),
+            fallible::query_parser_add_custom_boolean_prefix(
+                self.ptr.as_mut(),
                 &field,
                 field_proc.upcast(),
-                grouping,
+                grouping_ptr,
             )
-        }
+        }?;
+        self.field_procs.push(field_proc);
+        Ok(())
     }
 
     /// Add a boolean term prefix, allowing the user to restrict a search with a boolean filter
@@ -422,20 +491,25 @@ impl QueryParser {
         field: impl AsRef<str>,
         prefix: impl Into<Option<T>>,
         grouping: impl Into<Option<U>>,
-    ) where
+    ) -> crate::Result<()>
+    where
         T: AsRef<str> + Default,
         U: AsRef<str> + Default,
     {
         cxx::let_cxx_string!(field = field.as_ref());
         cxx::let_cxx_string!(prefix = prefix.into().unwrap_or_default().as_ref());
-        let grouping = grouping
-            .into()
-            .map_or(std::ptr::null(), |g| g.as_ref().to_cxx_string().into_raw());
-        unsafe {
-            self.0
-                .as_mut()
-                .add_boolean_prefix(&field, &prefix, grouping)
-        }
+        let grouping = grouping.into().map(|g| g.as_ref().to_cxx_string());
+        let grouping_ptr = grouping
+            .as_ref()
+            .map_or(std::ptr::null(), |g| &**g as *const _);
+        Ok(unsafe {
+            fallible::query_parser_add_boolean_prefix(
+                self.ptr.as_mut(),
+                &field,
+                &prefix,
+                grouping_ptr,
+            )
+        }?)
     }
 
     /// Register a RangeProcessor
@@ -451,69 +525,86 @@ impl QueryParser {
         use crate::ffi::ToCxxString;
         use crate::range::FfiRangeProcessor;
 
-        let range_proc = range_proc.to_ffi(slot, marker, is_suffix, can_repeat);
+        let mut range_proc = range_proc.to_ffi(slot, marker, is_suffix, can_repeat);
 
-        let grouping = grouping
-            .into()
-            .map(|g| g.to_cxx_string().into_raw())
-            .unwrap_or(std::ptr::null_mut());
+        let grouping = grouping.into().map(|g| g.to_cxx_string());
+        let grouping_ptr = grouping
+            .as_ref()
+            .map_or(std::ptr::null(), |g| &**g as *const _);
 
         unsafe {
             ffi::shim::query_parser_add_range_processor(
-                self.0.as_mut(),
+                self.ptr.as_mut(),
                 range_proc.upcast(),
-                grouping,
+                grouping_ptr,
             )
         }
+        self.range_procs.push(range_proc);
+    }
+
+    /// Set the database to be used for spelling correction, synonyms and wildcard expansion
+    pub fn set_database(&mut self, db: impl AsRef<ffi::Database>) {
+        self.ptr.as_mut().set_database(db.as_ref())
     }
 
     /// Set the [`Stem`][crate::Stem] to be used with this `QueryParser`
     pub fn set_stemmer(&mut self, stemmer: impl AsRef<ffi::Stem>) {
-        self.0.as_mut().set_stemmer(stemmer.as_ref())
+        self.ptr.as_mut().set_stemmer(stemmer.as_ref())
     }
 
     /// Set the [`StemStrategy`][crate::StemStrategy]
     pub fn set_stemming_strategy(&mut self, strategy: impl Into<ffi::QueryParser_stem_strategy>) {
-        self.0.as_mut().set_stemming_strategy(strategy.into())
+        self.ptr.as_mut().set_stemming_strategy(strategy.into())
     }
 
     /// Set the [`Stopper`][crate::Stopper] to be used with this `QueryParser`
+    ///
+    /// The stopper is owned by this `QueryParser`, replacing (and dropping) any previous stopper
     pub fn set_stopper<T: crate::Stopper + 'static>(&mut self, stopper: impl Into<Option<T>>) {
         use crate::term::FfiStopper;
-        let stopper = stopper.into().map_or(std::ptr::null(), |s| {
-            Deref::deref(&s.into_ffi().upcast()) as *const _
-        });
-        unsafe { ffi::shim::query_parser_set_stopper(self.0.as_mut(), stopper) }
+        let stopper = stopper.into().map(|s| s.into_ffi());
+        let stopper_ptr = stopper
+            .as_ref()
+            .map_or(std::ptr::null(), |s| Deref::deref(&s.upcast()) as *const _);
+        unsafe { ffi::shim::query_parser_set_stopper(self.ptr.as_mut(), stopper_ptr) }
+        self.stopper = stopper;
+    }
+
+    /// Get the spelling-corrected form of the most recently parsed query string
+    ///
+    /// Corrections are only made when parsing with
+    /// [`QueryParserFlags::SPELLING_CORRECTION`][crate::QueryParserFlags::SPELLING_CORRECTION],
+    /// and require a database to have been set via [`QueryParser::set_database`].
+    /// Returns `None` if no corrections were made
+    pub fn corrected_query_string(&self) -> Option<String> {
+        let corrected = self.ptr.get_corrected_query_string();
+        (!corrected.is_empty()).then(|| corrected.to_string())
     }
 
     /// Parse the given query text into a `Query` instance
     pub fn parse_query<T>(
         &mut self,
         query: impl AsRef<str>,
-        flags: impl Into<Option<ffi::QueryParser_feature_flag>>,
+        flags: impl Into<Option<QueryParserFlags>>,
         default_prefix: impl Into<Option<T>>,
-    ) -> Query
+    ) -> crate::Result<Query>
     where
         T: AsRef<str> + Default,
     {
         cxx::let_cxx_string!(query = query.as_ref());
         cxx::let_cxx_string!(default_prefix = default_prefix.into().unwrap_or_default().as_ref());
-        let flags = flags
-            .into()
-            .unwrap_or(ffi::QueryParser_feature_flag::FLAG_DEFAULT) as u32;
-        Query::from_ffi(
-            self.0
-                .as_mut()
-                .parse_query(&query, flags.into(), &default_prefix)
-                .within_box(),
-        )
+        let flags = flags.into().unwrap_or_default().bits();
+        let query =
+            fallible::query_parser_parse_query(self.ptr.as_mut(), &query, flags, &default_prefix)?;
+        Ok(Query::from_ffi(ffi::shim::query_clone(&query).within_box()))
     }
 
     /// Return an iterator over terms omitted from the query as stopwords
     pub fn stoplist(&self) -> crate::iter::TermIter {
         crate::iter::TermIter::new(
-            self.0.stoplist_begin().within_box(),
-            self.0.stoplist_end().within_box(),
+            self.ptr.stoplist_begin().within_box(),
+            self.ptr.stoplist_end().within_box(),
+            crate::iter::TermStats::None,
         )
     }
 
@@ -521,21 +612,27 @@ impl QueryParser {
     pub fn unstem(&self, term: impl AsRef<str>) -> crate::iter::TermIter {
         cxx::let_cxx_string!(term = term.as_ref());
         crate::iter::TermIter::new(
-            self.0.unstem_begin(&term).within_box(),
-            self.0.unstem_end(&term).within_box(),
+            self.ptr.unstem_begin(&term).within_box(),
+            self.ptr.unstem_end(&term).within_box(),
+            crate::iter::TermStats::None,
         )
     }
 }
 
 impl AsRef<ffi::QueryParser> for QueryParser {
     fn as_ref(&self) -> &ffi::QueryParser {
-        &self.0
+        &self.ptr
     }
 }
 
 impl Default for QueryParser {
     fn default() -> Self {
-        Self(ffi::QueryParser::new2().within_box())
+        Self {
+            ptr: ffi::QueryParser::new2().within_box(),
+            stopper: None,
+            field_procs: Vec::new(),
+            range_procs: Vec::new(),
+        }
     }
 }
 
@@ -5,46 +5,73 @@ use std::{cell::RefCell, rc::Rc};
 use bitflags::bitflags;
 
 #[cfg(feature = "chrono")]
-mod date {
-    use crate::ToValue;
+pub(crate) mod date {
+    use crate::{Epoch, ToValue};
 
-    /// Handle a range of dates
+    use bytes::Bytes;
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+    pub(crate) fn parse_date(value: &str) -> Option<NaiveDate> {
+        value.parse().ok()
+    }
+
+    // Timestamps with an offset are normalised to UTC, and naive timestamps are assumed to be UTC
+    pub(crate) fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+        match value.parse::<DateTime<FixedOffset>>() {
+            Ok(dt) => Some(dt.with_timezone(&Utc)),
+            Err(_) => value.parse::<NaiveDateTime>().ok().map(|dt| dt.and_utc()),
+        }
+    }
+
+    fn process<T, F: Fn(T) -> Bytes>(
+        start: &str,
+        end: &str,
+        parse: fn(&str) -> Option<T>,
+        serialize: F,
+    ) -> (Option<Bytes>, Option<Bytes>) {
+        (parse(start).map(&serialize), parse(end).map(&serialize))
+    }
+
+    /// Handle a range of dates such as `2024-01-01..2024-12-31`, matching values stored via
+    /// [`ToValue`] for `chrono::NaiveDate`
     pub struct DateRangeProcessor;
 
     impl super::RangeProcessor for DateRangeProcessor {
-        fn process_range(
-            &self,
-            start: &str,
-            end: &str,
-        ) -> (Option<bytes::Bytes>, Option<bytes::Bytes>) {
-            (
-                start
-                    .parse::<chrono::NaiveDate>()
-                    .ok()
-                    .map(|d| d.serialize()),
-                end.parse::<chrono::NaiveDate>().ok().map(|d| d.serialize()),
-            )
+        fn process_range(&self, start: &str, end: &str) -> (Option<Bytes>, Option<Bytes>) {
+            process(start, end, parse_date, |d| d.serialize())
         }
     }
 
-    /// Handle a range of timestamps
+    /// Handle a range of timestamps such as `2024-01-01T00:00:00..2024-01-01T12:00:00+02:00`,
+    /// matching values stored via [`ToValue`] for `chrono::NaiveDateTime`, `chrono::DateTime`
+    /// or `time::OffsetDateTime`
+    ///
+    /// Timestamps with an offset are normalised to UTC, and those without are assumed to be UTC
     pub struct DateTimeRangeProcessor;
 
     impl super::RangeProcessor for DateTimeRangeProcessor {
-        fn process_range(
-            &self,
-            start: &str,
-            end: &str,
-        ) -> (Option<bytes::Bytes>, Option<bytes::Bytes>) {
-            (
-                start
-                    .parse::<chrono::NaiveDateTime>()
-                    .ok()
-                    .map(|dt| dt.serialize()),
-                end.parse::<chrono::NaiveDateTime>()
-                    .ok()
-                    .map(|dt| dt.serialize()),
-            )
+        fn process_range(&self, start: &str, end: &str) -> (Option<Bytes>, Option<Bytes>) {
+            process(start, end, parse_datetime, |dt| dt.serialize())
+        }
+    }
+
+    /// Handle a range of dates like [`DateRangeProcessor`], matching values stored as
+    /// [`Epoch`]`<chrono::NaiveDate>`
+    pub struct EpochDateRangeProcessor;
+
+    impl super::RangeProcessor for EpochDateRangeProcessor {
+        fn process_range(&self, start: &str, end: &str) -> (Option<Bytes>, Option<Bytes>) {
+            process(start, end, parse_date, |d| Epoch(d).serialize())
+        }
+    }
+
+    /// Handle a range of timestamps like [`DateTimeRangeProcessor`], matching values stored as
+    /// [`Epoch`] timestamps
+    pub struct EpochDateTimeRangeProcessor;
+
+    impl super::RangeProcessor for EpochDateTimeRangeProcessor {
+        fn process_range(&self, start: &str, end: &str) -> (Option<Bytes>, Option<Bytes>) {
+            process(start, end, parse_datetime, |dt| Epoch(dt).serialize())
         }
     }
 }
@@ -52,7 +79,30 @@ mod date {
 #[cfg(feature = "chrono")]
 pub use date::*;
 
-/// Handle a numeric range
+/// Handle a range of signed integers, matching values stored via [`ToValue`] for `i64` or `isize`
+pub struct IntegerRangeProcessor;
+
+impl RangeProcessor for IntegerRangeProcessor {
+    fn process_range(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> (Option<bytes::Bytes>, Option<bytes::Bytes>) {
+        (
+            start.parse::<i64>().ok().map(|x| x.serialize()),
+            end.parse::<i64>().ok().map(|x| x.serialize()),
+        )
+    }
+}
+
+/// Handle a numeric range, matching values stored via [`ToValue`] for floating point numbers and
+/// integers of up to 32 bits
+///
+/// Bounds are parsed as `f64`. Values stored as `f32` are widened to `f64` first, so a bound such
+/// as `0.1` is not exactly equal to a stored `0.1f32`
+///
+/// 64-bit integers use a different encoding; see [`IntegerRangeProcessor`] and
+/// [`UnsignedRangeProcessor`]
 pub struct NumberRangeProcessor;
 
 impl RangeProcessor for NumberRangeProcessor {
@@ -63,8 +113,8 @@ impl RangeProcessor for NumberRangeProcessor {
     ) -> (Option<bytes::Bytes>, Option<bytes::Bytes>) {
         use crate::ToValue;
         (
-            start.parse::<f32>().ok().map(|x| x.serialize()),
-            end.parse::<f32>().ok().map(|x| x.serialize()),
+            start.parse::<f64>().ok().map(|x| x.serialize()),
+            end.parse::<f64>().ok().map(|x| x.serialize()),
         )
     }
 }
@@ -84,10 +134,8 @@ pub(crate) trait FfiRangeProcessor: RangeProcessor + Sized + 'static {
         marker: impl Into<String>,
         is_suffix: bool,
         can_repeat: bool,
-    ) -> &'static mut RangeProcessorObj {
-        Box::leak(Box::new(RangeProcessorObj::new(
-            slot, marker, is_suffix, can_repeat, self,
-        )))
+    ) -> RangeProcessorObj {
+        RangeProcessorObj::new(slot, marker, is_suffix, can_repeat, self)
     }
 }
 
@@ -151,6 +199,23 @@ impl RangeProcessorObj {
     }
 }
 
+/// Handle a range of unsigned integers, matching values stored via [`ToValue`] for `u64` or
+/// `usize`
+pub struct UnsignedRangeProcessor;
+
+impl RangeProcessor for UnsignedRangeProcessor {
+    fn process_range(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> (Option<bytes::Bytes>, Option<bytes::Bytes>) {
+        (
+            start.parse::<u64>().ok().map(|x| x.serialize()),
+            end.parse::<u64>().ok().map(|x| x.serialize()),
+        )
+    }
+}
+
 /// Handle a string range (effectively a no-op, relying on lexical sorting)
 pub struct StringRangeProcessor;
 
@@ -0,0 +1,432 @@
+use crate::{
+    boolean_term, Document, Error, ErrorInfo, QueryParser, Stem, StemStrategy, TermGenerator,
+    ToValue,
+};
+
+use std::collections::HashSet;
+
+use bytes::Bytes;
+
+/// How a field is encoded in its value slot, which determines how the values passed to
+/// [`Schema::index`] and the ranges understood by [`Schema::query_parser`] are parsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    /// A signed integer, encoded losslessly via [`ToValue`] for `i64` and searched via
+    /// [`IntegerRangeProcessor`][crate::IntegerRangeProcessor]
+    Integer,
+    /// A number, encoded via [`ToValue`] for `f64` and searched via
+    /// [`NumberRangeProcessor`][crate::NumberRangeProcessor]
+    Number,
+    /// A string, stored as-is and searched via
+    /// [`StringRangeProcessor`][crate::StringRangeProcessor]
+    String,
+    /// An unsigned integer, encoded losslessly via [`ToValue`] for `u64` and searched via
+    /// [`UnsignedRangeProcessor`][crate::UnsignedRangeProcessor]
+    Unsigned,
+    /// A date such as `2024-01-31`, searched via
+    /// [`DateRangeProcessor`][crate::DateRangeProcessor]
+    #[cfg(feature = "chrono")]
+    Date,
+    /// A timestamp such as `2024-01-31T12:00:00`, or `2024-01-31T12:00:00+02:00` with an offset
+    /// (normalised to UTC), searched via
+    /// [`DateTimeRangeProcessor`][crate::DateTimeRangeProcessor]
+    #[cfg(feature = "chrono")]
+    DateTime,
+}
+
+impl ValueType {
+    fn encode(self, value: &str) -> Option<Bytes> {
+        match self {
+            ValueType::Integer => value.trim().parse::<i64>().ok().map(|x| x.serialize()),
+            ValueType::Number => value.trim().parse::<f64>().ok().map(|x| x.serialize()),
+            ValueType::String => Some(value.serialize()),
+            ValueType::Unsigned => value.trim().parse::<u64>().ok().map(|x| x.serialize()),
+            // Parsed the same way as the bounds of the field's range processor
+            #[cfg(feature = "chrono")]
+            ValueType::Date => crate::range::date::parse_date(value).map(|d| d.serialize()),
+            #[cfg(feature = "chrono")]
+            ValueType::DateTime => {
+                crate::range::date::parse_datetime(value).map(|dt| dt.serialize())
+            }
+        }
+    }
+
+    fn add_rangeprocessor(self, qp: &mut QueryParser, marker: String, slot: u32) {
+        match self {
+            ValueType::Integer => qp.add_rangeprocessor(
+                marker,
+                slot,
+                crate::IntegerRangeProcessor,
+                false,
+                false,
+                None,
+            ),
+            ValueType::Number => qp.add_rangeprocessor(
+                marker,
+                slot,
+                crate::NumberRangeProcessor,
+                false,
+                false,
+                None,
+            ),
+            ValueType::String => qp.add_rangeprocessor(
+                marker,
+                slot,
+                crate::StringRangeProcessor,
+                false,
+                false,
+                None,
+            ),
+            ValueType::Unsigned => qp.add_rangeprocessor(
+                marker,
+                slot,
+                crate::UnsignedRangeProcessor,
+                false,
+                false,
+                None,
+            ),
+            #[cfg(feature = "chrono")]
+            ValueType::Date => {
+                qp.add_rangeprocessor(marker, slot, crate::DateRangeProcessor, false, false, None)
+            }
+            #[cfg(feature = "chrono")]
+            ValueType::DateTime => qp.add_rangeprocessor(
+                marker,
+                slot,
+                crate::DateTimeRangeProcessor,
+                false,
+                false,
+                None,
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FieldKind {
+    Text,
+    Boolean,
+    Value,
+}
+
+/// A field declared in a [`Schema`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    name: String,
+    kind: FieldKind,
+    prefix: String,
+    weight: Option<u32>,
+    slot: Option<(u32, ValueType)>,
+    stored: bool,
+}
+
+impl Field {
+    fn new(name: impl Into<String>, kind: FieldKind, prefix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            prefix: prefix.into(),
+            weight: None,
+            slot: None,
+            stored: false,
+        }
+    }
+
+    /// Declare a free-text field, which is indexed with `prefix` and searched via `name:...`
+    ///
+    /// An empty `prefix` indexes the field alongside unprefixed text, so that it is also matched
+    /// by queries without a field
+    pub fn text(name: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self::new(name, FieldKind::Text, prefix)
+    }
+
+    /// Declare a boolean field, whose whole value is added as a single term with `prefix` (see
+    /// [`boolean_term`]), and which filters queries via `name:...`
+    pub fn boolean(name: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self::new(name, FieldKind::Boolean, prefix)
+    }
+
+    /// Declare a field which is only stored in value slot `slot`, for sorting and for range
+    /// queries via `name:start..end`
+    pub fn value(name: impl Into<String>, slot: u32, value_type: ValueType) -> Self {
+        Self::new(name, FieldKind::Value, "").slot(slot, value_type)
+    }
+
+    /// Also store the field in value slot `slot`, for sorting and for range queries via
+    /// `name:start..end`
+    pub fn slot(mut self, slot: u32, value_type: ValueType) -> Self {
+        self.slot = Some((slot, value_type));
+        self
+    }
+
+    /// Keep the original value of the field in the document data, so that it can be retrieved via
+    /// [`Schema::stored_fields`]
+    pub fn stored(mut self) -> Self {
+        self.stored = true;
+        self
+    }
+
+    /// Increase the within-document-frequency of the terms in a free-text field by `weight`
+    /// (rather than 1) per occurrence
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Returns true if this is a boolean field
+    pub fn is_boolean(&self) -> bool {
+        self.kind == FieldKind::Boolean
+    }
+
+    /// Returns true if the original value of this field is kept in the document data
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true if this is a free-text field
+    pub fn is_text(&self) -> bool {
+        self.kind == FieldKind::Text
+    }
+
+    /// The name of this field, as used in queries
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The term prefix of this field, which is empty for fields only stored in a value slot
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The value slot (if any) of this field
+    pub fn slot_number(&self) -> Option<u32> {
+        self.slot.map(|(slot, _)| slot)
+    }
+
+    /// The type of the value (if any) stored for this field
+    pub fn value_type(&self) -> Option<ValueType> {
+        self.slot.map(|(_, value_type)| value_type)
+    }
+}
+
+/// A declaration of the fields in an index, which keeps the prefixes and value slots used when
+/// indexing documents consistent with those understood when parsing queries
+///
+/// Stemming applies to every free-text field, since Xapian configures it per
+/// [`TermGenerator`] and [`QueryParser`] rather than per prefix
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Schema {
+    fields: Vec<Field>,
+    language: Option<String>,
+    stemming_strategy: Option<StemStrategy>,
+}
+
+impl Schema {
+    /// Create an empty schema, without stemming
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the schema
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Stem free text using the stemmer for `language`
+    pub fn stemmer(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set the stemming strategy used when indexing and parsing free text
+    pub fn stemming_strategy(mut self, strategy: StemStrategy) -> Self {
+        self.stemming_strategy = Some(strategy);
+        self
+    }
+
+    /// Get the fields in this schema, in the order they were declared
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Look up a field by name
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name.as_ref())
+    }
+
+    /// Build a document from `(field, value)` pairs, using `indexer` to index free text
+    ///
+    /// A field may be given more than once, such as a boolean field with several values.
+    /// Returns an [`Error::InvalidArgument`] for unknown fields, or values which can't be parsed
+    /// as the field's [`ValueType`]
+    pub fn index<K, V>(
+        &self,
+        indexer: &mut TermGenerator,
+        values: impl IntoIterator<Item = (K, V)>,
+    ) -> crate::Result<Document>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.validate()?;
+
+        let mut doc = Document::default();
+        indexer.set_document(&doc);
+        let mut stored = Vec::new();
+        let mut has_text = false;
+
+        for (name, value) in values {
+            let (name, value) = (name.as_ref(), value.as_ref());
+            let field = self
+                .get(name)
+                .ok_or_else(|| Error::invalid_argument(format!("unknown field `{name}`")))?;
+
+            match field.kind {
+                FieldKind::Text => {
+                    // Keep phrases from spanning separate fields
+                    if has_text {
+                        indexer.increase_termpos(None);
+                    }
+                    has_text = true;
+                    indexer.index_text(value, field.weight, field.prefix.as_str())?;
+                }
+                FieldKind::Boolean => doc.add_boolean_term(boolean_term(&field.prefix, value))?,
+                FieldKind::Value => {}
+            }
+
+            if let Some((slot, value_type)) = field.slot {
+                let encoded = value_type.encode(value).ok_or_else(|| {
+                    Error::invalid_argument(format!(
+                        "field `{name}`: invalid {value_type:?} `{value}`"
+                    ))
+                })?;
+                doc.set_value(slot, encoded)?;
+            }
+
+            if field.stored {
+                if value.contains('\0') {
+                    return Err(Error::invalid_argument(format!(
+                        "field `{name}`: stored values must not contain NUL"
+                    )));
+                }
+                stored.extend_from_slice(name.as_bytes());
+                stored.push(0);
+                stored.extend_from_slice(value.as_bytes());
+                stored.push(0);
+            }
+        }
+
+        if !stored.is_empty() {
+            doc.set_data(stored);
+        }
+        Ok(doc)
+    }
+
+    /// Build a [`QueryParser`] which understands every field in this schema
+    ///
+    /// Free-text and boolean fields are searched via `name:...`, and fields with a value slot via
+    /// `name:start..end`
+    pub fn query_parser(&self) -> crate::Result<QueryParser> {
+        self.validate()?;
+
+        let mut qp = QueryParser::default();
+        if let Some(language) = &self.language {
+            qp.set_stemmer(Stem::for_language(language)?);
+        }
+        if let Some(strategy) = self.stemming_strategy {
+            qp.set_stemming_strategy(strategy);
+        }
+
+        for field in &self.fields {
+            match field.kind {
+                FieldKind::Text => qp.add_prefix(&field.name, field.prefix.as_str())?,
+                FieldKind::Boolean => {
+                    qp.add_boolean_prefix(&field.name, field.prefix.as_str(), None::<&str>)?
+                }
+                FieldKind::Value => {}
+            }
+            if let Some((slot, value_type)) = field.slot {
+                value_type.add_rangeprocessor(&mut qp, format!("{}:", field.name), slot);
+            }
+        }
+        Ok(qp)
+    }
+
+    /// Retrieve the stored fields (see [`Field::stored`]) from a document built by
+    /// [`Schema::index`], in the order they were indexed
+    pub fn stored_fields(doc: &Document) -> crate::Result<Vec<(String, String)>> {
+        let data = doc.data()?;
+        let mut parts = data.split(|b| *b == 0);
+        let mut fields = Vec::new();
+        while let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+            let decode = |part: &[u8]| {
+                String::from_utf8(part.to_vec()).map_err(|e| {
+                    Error::Serialisation(ErrorInfo::new(format!("invalid stored field: {e}")))
+                })
+            };
+            fields.push((decode(name)?, decode(value)?));
+        }
+        Ok(fields)
+    }
+
+    /// Build a [`TermGenerator`] which indexes free text with this schema's stemming settings
+    pub fn term_generator(&self) -> crate::Result<TermGenerator> {
+        self.validate()?;
+
+        let mut indexer = TermGenerator::default();
+        if let Some(language) = &self.language {
+            indexer.set_stemmer(Stem::for_language(language)?);
+        }
+        if let Some(strategy) = self.stemming_strategy {
+            indexer.set_stemming_strategy(strategy);
+        }
+        Ok(indexer)
+    }
+
+    /// Check that the fields in this schema don't conflict with one another
+    ///
+    /// Field names and value slots must be unique, non-empty prefixes may only be used by a single
+    /// field, and boolean fields must have a prefix
+    pub fn validate(&self) -> crate::Result<()> {
+        let mut names = HashSet::new();
+        let mut prefixes = HashSet::new();
+        let mut slots = HashSet::new();
+
+        for field in &self.fields {
+            if field.name.is_empty() || field.name.contains(['\0', ':']) {
+                return Err(Error::invalid_argument(format!(
+                    "invalid field name `{}`",
+                    field.name
+                )));
+            }
+            if !names.insert(field.name.as_str()) {
+                return Err(Error::invalid_argument(format!(
+                    "duplicate field `{}`",
+                    field.name
+                )));
+            }
+            if field.is_boolean() && field.prefix.is_empty() {
+                return Err(Error::invalid_argument(format!(
+                    "boolean field `{}` requires a prefix",
+                    field.name
+                )));
+            }
+            if !field.prefix.is_empty() && !prefixes.insert(field.prefix.as_str()) {
+                return Err(Error::invalid_argument(format!(
+                    "field `{}` reuses prefix `{}`",
+                    field.name, field.prefix
+                )));
+            }
+            if let Some(slot) = field.slot_number() {
+                if !slots.insert(slot) {
+                    return Err(Error::invalid_argument(format!(
+                        "field `{}` reuses value slot {slot}",
+                        field.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
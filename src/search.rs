@@ -1,4 +1,7 @@
-use crate::{ffi, DocId};
+use crate::{
+    ffi::{self, fallible},
+    DocId,
+};
 
 use std::{
     cell::{Ref, RefCell},
@@ -13,20 +16,38 @@ use autocxx::{cxx, prelude::*};
 /// The primary interface to retrieve information from Xapian.
 ///
 /// Used to perform searches, faceting, term iteration, expansion, sorting, relevancy and more.
-pub struct Enquire(Pin<Box<ffi::Enquire>>);
+pub struct Enquire {
+    // Xapian only borrows the match spies, so they must outlive (and therefore be dropped after)
+    // the underlying Enquire
+    ptr: Pin<Box<ffi::Enquire>>,
+    spies: Vec<MatchSpyObj>,
+    shards: u32,
+}
 
 impl Enquire {
     /// Create a new `Enquire` instance associated with the given `db`
-    pub fn new(db: impl AsRef<ffi::Database>) -> Self {
-        Self(ffi::Enquire::new2(db.as_ref()).within_box())
+    pub fn new(db: impl crate::Shards) -> Self {
+        Self {
+            ptr: ffi::Enquire::new2(db.as_ref()).within_box(),
+            spies: Vec::new(),
+            shards: db.shard_count(),
+        }
     }
 
     /// Attach a [`MatchSpy`] implementation to this `Enquire`
     ///
-    /// Instances of `MatchSpy` can be used to implement faceting
+    /// Instances of `MatchSpy` can be used to implement faceting. The spy is owned by this
+    /// `Enquire` until it is dropped, or [`Enquire::clear_matchspies`] is called.
     pub fn add_matchspy<T: crate::MatchSpy + Clone + 'static>(&mut self, spy: &T) {
-        let spy = spy.clone().into_ffi();
-        unsafe { ffi::shim::enquire_add_matchspy(self.0.as_mut(), spy.upcast()) }
+        let mut spy = spy.clone().into_ffi();
+        unsafe { ffi::shim::enquire_add_matchspy(self.ptr.as_mut(), spy.upcast()) }
+        self.spies.push(spy);
+    }
+
+    /// Detach (and drop) all [`MatchSpy`] implementations attached to this `Enquire`
+    pub fn clear_matchspies(&mut self) {
+        self.ptr.as_mut().clear_matchspies();
+        self.spies.clear();
     }
 
     /// Retrieve the term expansion set for this Enquire
@@ -39,24 +60,23 @@ impl Enquire {
         flags: i32,
         decider: impl Into<Option<D>>,
         min_wt: f64,
-    ) -> ESet {
-        let decider = decider.into().map_or(std::ptr::null(), |d| {
-            Deref::deref(&d.into_ffi().upcast()) as *const _
-        });
-
-        ESet(
-            unsafe {
-                ffi::shim::enquire_get_eset(
-                    &self.0,
-                    maxitems.into(),
-                    rset.as_ref(),
-                    flags.into(),
-                    decider,
-                    min_wt,
-                )
-            }
-            .within_box(),
-        )
+    ) -> crate::Result<ESet> {
+        let decider = decider.into().map(|d| d.into_ffi());
+        let decider_ptr = decider
+            .as_ref()
+            .map_or(std::ptr::null(), |d| Deref::deref(&d.upcast()) as *const _);
+
+        let eset = unsafe {
+            fallible::enquire_get_eset(
+                &self.ptr,
+                maxitems,
+                rset.as_ref(),
+                flags,
+                decider_ptr,
+                min_wt,
+            )
+        }?;
+        Ok(ESet(ffi::shim::eset_copy(&eset).within_box()))
     }
 
     /// Retrieve the [`MSet`] for the current [`Query`][crate::Query] with the default MatchDecider
@@ -66,24 +86,26 @@ impl Enquire {
         maxitems: u32,
         atleast: impl Into<Option<u32>>,
         rset: impl Into<Option<RSet>>,
-    ) -> MSet {
-        let rset = rset
-            .into()
-            .map_or(std::ptr::null(), |r| r.as_ref() as *const _);
-
-        MSet::new(
-            unsafe {
-                ffi::shim::enquire_get_mset(
-                    &self.0,
-                    first.into(),
-                    maxitems.into(),
-                    atleast.into().unwrap_or(0).into(),
-                    rset,
-                    std::ptr::null(),
-                )
-            }
-            .within_box(),
-        )
+    ) -> crate::Result<MSet> {
+        let rset = rset.into();
+        let rset_ptr = rset
+            .as_ref()
+            .map_or(std::ptr::null(), |r| r.as_ref() as *const ffi::RSet);
+
+        let mset = unsafe {
+            fallible::enquire_get_mset(
+                &self.ptr,
+                first,
+                maxitems,
+                atleast.into().unwrap_or(0),
+                rset_ptr,
+                std::ptr::null(),
+            )
+        }?;
+        Ok(MSet::new(
+            ffi::shim::mset_copy(&mset).within_box(),
+            self.shards,
+        ))
     }
 
     /// Retrieve the [`MSet`] for the current [`Query`][crate::Query] with a custom MatchDecider
@@ -94,36 +116,41 @@ impl Enquire {
         atleast: impl Into<Option<u32>>,
         rset: impl Into<Option<RSet>>,
         decider: impl MatchDecider + 'static,
-    ) -> MSet {
-        let rset = rset
-            .into()
-            .map_or(std::ptr::null(), |r| r.as_ref() as *const _);
-
-        let decider = Deref::deref(&decider.into_ffi().upcast()) as *const _;
-
-        MSet::new(
-            unsafe {
-                ffi::shim::enquire_get_mset(
-                    &self.0,
-                    first.into(),
-                    maxitems.into(),
-                    atleast.into().unwrap_or(0).into(),
-                    rset,
-                    decider,
-                )
-            }
-            .within_box(),
-        )
+    ) -> crate::Result<MSet> {
+        let rset = rset.into();
+        let rset_ptr = rset
+            .as_ref()
+            .map_or(std::ptr::null(), |r| r.as_ref() as *const ffi::RSet);
+
+        // The decider is only consulted while the MSet is being built, so it is dropped as soon
+        // as this call completes
+        let decider = decider.into_ffi();
+        let decider_ptr = Deref::deref(&decider.upcast()) as *const _;
+
+        let mset = unsafe {
+            fallible::enquire_get_mset(
+                &self.ptr,
+                first,
+                maxitems,
+                atleast.into().unwrap_or(0),
+                rset_ptr,
+                decider_ptr,
+            )
+        }?;
+        Ok(MSet::new(
+            ffi::shim::mset_copy(&mset).within_box(),
+            self.shards,
+        ))
     }
 
     /// Retrieve the query currently associated with this Enquire instance
     pub fn query(&self) -> crate::Query {
-        crate::Query::from_ffi(ffi::shim::query_clone(self.0.get_query()).within_box())
+        crate::Query::from_ffi(ffi::shim::query_clone(self.ptr.get_query()).within_box())
     }
 
     /// Set the query currently associated with this Enquire instance
     pub fn set_query(&mut self, query: impl AsRef<ffi::Query>, qlen: impl Into<Option<u32>>) {
-        self.0
+        self.ptr
             .as_mut()
             .set_query(query.as_ref(), qlen.into().unwrap_or(0).into());
     }
@@ -131,7 +158,7 @@ impl Enquire {
 
 impl AsRef<ffi::Enquire> for Enquire {
     fn as_ref(&self) -> &ffi::Enquire {
-        &self.0
+        &self.ptr
     }
 }
 
@@ -142,8 +169,8 @@ pub trait ExpandDecider {
 }
 
 trait FfiExpandDecider: ExpandDecider + Sized + 'static {
-    fn into_ffi(self) -> &'static ExpandDeciderObj {
-        Box::leak(Box::new(ExpandDeciderObj::from(self)))
+    fn into_ffi(self) -> ExpandDeciderObj {
+        ExpandDeciderObj::from(self)
     }
 }
 
@@ -205,12 +232,13 @@ impl ESet {
 pub struct Match {
     value: ffi::docid,
     ptr: Pin<Box<ffi::MSetIterator>>,
+    shards: u32,
 }
 
 impl Match {
-    pub(crate) fn new(ptr: Pin<Box<ffi::MSetIterator>>) -> Self {
+    pub(crate) fn new(ptr: Pin<Box<ffi::MSetIterator>>, shards: u32) -> Self {
         let value = ffi::shim::mset_iterator_docid(&ptr);
-        Self { value, ptr }
+        Self { value, ptr, shards }
     }
 
     /// Retrieve the [`DocId`][crate::DocId] associated with this Match
@@ -219,8 +247,11 @@ impl Match {
     }
 
     /// Retrieve the [`Document`][crate::Document] associated with this Match
-    pub fn document(&self) -> crate::Document {
-        crate::Document::new(self.ptr.get_document().within_box())
+    pub fn document(&self) -> crate::Result<crate::Document> {
+        let doc = fallible::mset_iterator_get_document(&self.ptr)?;
+        Ok(crate::Document::new(
+            ffi::shim::document_copy(&doc).within_box(),
+        ))
     }
 
     /// Retrieve the weight of this Match, represented as a percentage
@@ -233,6 +264,23 @@ impl Match {
         self.ptr.get_rank().into()
     }
 
+    /// Retrieve the [`DocId`][crate::DocId] of this Match within the shard which contains it
+    ///
+    /// When searching a single database, this is the same as [`Match::docid`]
+    pub fn shard_docid(&self) -> crate::DocId {
+        let id = u32::from(self.docid());
+        unsafe { crate::DocId::new_unchecked((id - 1) / self.shards.max(1) + 1) }
+    }
+
+    /// Retrieve the index of the shard which contains this Match
+    ///
+    /// Shards are numbered from zero, in the order they were added to the searched
+    /// [`Database`][crate::Database]
+    pub fn shard_index(&self) -> u32 {
+        let id = u32::from(self.docid());
+        (id - 1) % self.shards.max(1)
+    }
+
     /// Retrieve the weight of this Match
     pub fn weight(&self) -> f64 {
         self.ptr.get_weight()
@@ -266,8 +314,8 @@ pub trait MatchDecider {
 }
 
 trait FfiMatchDecider: MatchDecider + Sized + 'static {
-    fn into_ffi(self) -> &'static MatchDeciderObj {
-        Box::leak(Box::new(MatchDeciderObj::from(self)))
+    fn into_ffi(self) -> MatchDeciderObj {
+        MatchDeciderObj::from(self)
     }
 }
 
@@ -312,8 +360,8 @@ pub trait MatchSpy {
 }
 
 trait FfiMatchSpy: MatchSpy + Sized + 'static {
-    fn into_ffi(self) -> &'static mut MatchSpyObj {
-        Box::leak(Box::new(MatchSpyObj::from(self)))
+    fn into_ffi(self) -> MatchSpyObj {
+        MatchSpyObj::from(self)
     }
 }
 
@@ -344,29 +392,36 @@ impl<T: MatchSpy + 'static> From<T> for MatchSpyObj {
 }
 
 /// A list of search results with associated metadata
-pub struct MSet(Pin<Box<ffi::MSet>>);
+pub struct MSet {
+    ptr: Pin<Box<ffi::MSet>>,
+    shards: u32,
+}
 
 impl MSet {
-    pub(crate) fn new(ptr: Pin<Box<ffi::MSet>>) -> Self {
-        Self(ptr)
+    pub(crate) fn new(ptr: Pin<Box<ffi::MSet>>, shards: u32) -> Self {
+        Self { ptr, shards }
     }
 
     pub(crate) fn begin(&self) -> Pin<Box<ffi::MSetIterator>> {
-        self.0.begin().within_box()
+        self.ptr.begin().within_box()
     }
 
     pub(crate) fn end(&self) -> Pin<Box<ffi::MSetIterator>> {
-        self.0.end().within_box()
+        self.ptr.end().within_box()
+    }
+
+    pub(crate) fn shards(&self) -> u32 {
+        self.shards
     }
 
     /// Convert a weight to a percentage, taking into account weighted query terms
     pub fn convert_to_percent(&self, weight: f64) -> i32 {
-        self.0.convert_to_percent(weight).into()
+        self.ptr.convert_to_percent(weight).into()
     }
 
     /// Detects whether this `MSet` is empty
     pub fn empty(&self) -> bool {
-        self.0.empty()
+        self.ptr.empty()
     }
 
     /// Retrieve the iterator of [`Match`] objects for this `MSet`
@@ -376,7 +431,7 @@ impl MSet {
 
     /// The number of matches in this `MSet`
     pub fn size(&self) -> u32 {
-        self.0.size().into()
+        self.ptr.size().into()
     }
 
     /// Generate a snippet from the provided `text`
@@ -395,7 +450,7 @@ impl MSet {
         flags: u32,
         hl: impl Into<Option<(T, U)>>,
         omit: impl Into<Option<V>>,
-    ) -> String
+    ) -> crate::Result<String>
     where
         T: AsRef<str> + Default,
         U: AsRef<str> + Default,
@@ -406,23 +461,24 @@ impl MSet {
         cxx::let_cxx_string!(hl_start = hl_start.as_ref());
         cxx::let_cxx_string!(hl_end = hl_end.as_ref());
         cxx::let_cxx_string!(omit = omit.into().unwrap_or_default().as_ref());
-        let text = self.0.snippet(
+        let text = fallible::mset_snippet(
+            &self.ptr,
             &text,
             length,
             stemmer.as_ref(),
-            flags.into(),
+            flags,
             &hl_start,
             &hl_end,
             &omit,
-        );
+        )?;
 
-        text.to_string()
+        Ok(text.to_string())
     }
 
     /// Get the number of documents which `term` occurs in
-    pub fn termfreq(&self, term: impl AsRef<str>) -> u32 {
+    pub fn termfreq(&self, term: impl AsRef<str>) -> crate::Result<u32> {
         cxx::let_cxx_string!(term = term.as_ref());
-        self.0.get_termfreq(&term).into()
+        Ok(fallible::mset_get_termfreq(&self.ptr, &term)?)
     }
 }
 
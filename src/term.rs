@@ -1,4 +1,4 @@
-use crate::ffi;
+use crate::ffi::{self, fallible};
 
 use std::{
     cell::{Ref, RefCell},
@@ -76,6 +76,7 @@ impl PartialOrd for Expansion {
     }
 }
 /// A strategy to apply to a `Stem` instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StemStrategy {
     /// Generate only unstemmed terms
     None,
@@ -172,9 +173,10 @@ impl Stem {
     }
 
     /// Returns a stemmer instance for the given language, if one exists
-    pub fn for_language(lang: impl AsRef<str>) -> Self {
+    pub fn for_language(lang: impl AsRef<str>) -> crate::Result<Self> {
         cxx::let_cxx_string!(lang = lang.as_ref());
-        Self(ffi::Stem::new3(&lang).within_box())
+        let stem = fallible::stem_for_language(&lang)?;
+        Ok(Self(ffi::shim::stem_copy(&stem).within_box()))
     }
 
     /// Run the underlying stem algorithm against `word`, returning its stemmed form
@@ -230,8 +232,8 @@ pub trait Stopper {
 }
 
 pub(crate) trait FfiStopper: Stopper + Sized + 'static {
-    fn into_ffi(self) -> &'static StopperObj {
-        Box::leak(Box::new(StopperObj::from(self)))
+    fn into_ffi(self) -> StopperObj {
+        StopperObj::from(self)
     }
 }
 
@@ -273,53 +275,149 @@ impl<T: Stopper + 'static> From<T> for StopperObj {
 }
 
 /// An individual `term`, with access to position and frequency information
+///
+/// Statistics are read when the term is produced, since the iterator which produced it may move
+/// on before they are used. Only those which are meaningful for the term's source are available;
+/// the rest return [`Error::InvalidOperation`][crate::Error::InvalidOperation]
 pub struct Term {
     value: UniquePtr<CxxString>,
-    ptr: Pin<Box<ffi::TermIterator>>,
+    frequency: crate::Result<u32>,
+    wdf: crate::Result<u32>,
+    positions: crate::Result<Vec<u32>>,
+    db: Option<Rc<Pin<Box<ffi::Database>>>>,
 }
 
 impl Term {
-    pub(crate) fn new(ptr: Pin<Box<ffi::TermIterator>>) -> Self {
-        let value = ffi::shim::term_iterator_term(&ptr);
-        Self { value, ptr }
+    pub(crate) fn new(
+        ptr: Pin<Box<ffi::TermIterator>>,
+        stats: crate::iter::TermStats,
+        db: Option<Rc<Pin<Box<ffi::Database>>>>,
+    ) -> crate::Result<Self> {
+        use crate::iter::TermStats;
+
+        let value = fallible::term_iterator_term(&ptr)?;
+        let frequency = match stats {
+            TermStats::Frequency | TermStats::Document { frequency: true } => {
+                fallible::term_iterator_get_termfreq(&ptr).map_err(Into::into)
+            }
+            _ => Err(unavailable("term frequency")),
+        };
+        let (wdf, positions) = match stats {
+            TermStats::Document { .. } => (
+                fallible::term_iterator_get_wdf(&ptr).map_err(Into::into),
+                fallible::term_iterator_positionlist_begin(&ptr)
+                    .map_err(crate::Error::from)
+                    .and_then(|begin| {
+                        crate::iter::PositionIter::read(
+                            ffi::shim::position_iterator_copy(&begin).within_box(),
+                            ptr.positionlist_end().within_box(),
+                        )
+                    }),
+            ),
+            _ => (
+                Err(unavailable("within-document frequency")),
+                Err(unavailable("positional information")),
+            ),
+        };
+
+        Ok(Self {
+            value,
+            frequency,
+            wdf,
+            positions,
+            db,
+        })
+    }
+
+    /// Get the total number of occurrences of this term across all documents
+    ///
+    /// This is only available for terms from [`Database::allterms`][crate::Database::allterms],
+    /// and returns `None` for any other term, including those of a
+    /// [`Document`][crate::Document] read from a database
+    pub fn collection_frequency(&self) -> crate::Result<Option<u32>> {
+        self.db
+            .as_ref()
+            .map(|db| Ok(fallible::database_get_collection_freq(db, &self.value)?))
+            .transpose()
     }
 
-    /// Get the frequency of this term
-    pub fn frequency(&self) -> u32 {
-        self.ptr.get_termfreq().into()
+    /// Get the frequency of this term (the number of documents it is indexed by)
+    ///
+    /// For terms from [`Database::spellings`][crate::Database::spellings], this is the frequency
+    /// of the word in the spelling dictionary. Terms from a [`Document`][crate::Document] only
+    /// have a frequency if the document was read from a database
+    pub fn frequency(&self) -> crate::Result<u32> {
+        self.frequency.clone()
     }
 
     /// Get the number of occurrences of this term
-    pub fn positions_len(&self) -> u32 {
-        self.ptr.positionlist_count().into()
+    pub fn positions_len(&self) -> crate::Result<u32> {
+        self.positions
+            .as_ref()
+            .map(|p| p.len() as u32)
+            .map_err(Clone::clone)
     }
 
     /// Get an iterator over the specific occurrences of this term
-    pub fn positions(&self) -> crate::iter::PositionIter {
-        crate::iter::PositionIter::new(self)
+    pub fn positions(&self) -> crate::Result<crate::iter::PositionIter> {
+        self.positions.clone().map(crate::iter::PositionIter::new)
     }
 
     /// Get the within-document-frequency for this term
-    pub fn wdf(&self) -> u32 {
-        self.ptr.get_wdf().into()
+    pub fn wdf(&self) -> crate::Result<u32> {
+        self.wdf.clone()
+    }
+}
+
+fn unavailable(stat: &str) -> crate::Error {
+    crate::Error::InvalidOperation(crate::ErrorInfo::new(format!(
+        "{stat} is not available for this term"
+    )))
+}
+
+/// Build the boolean term for `value` with `prefix`, the same way [`QueryParser`] does for
+/// boolean prefixes
+///
+/// Following Xapian's convention, a `:` is inserted between a prefix of more than one character
+/// (which doesn't already end in `:`) and a value starting with an uppercase letter or `:`, so
+/// that the value can't be mistaken for part of the prefix
+///
+/// [`QueryParser`]: crate::QueryParser
+pub fn boolean_term(prefix: &str, value: &str) -> String {
+    match value.chars().next() {
+        Some(first) if prefix_needs_colon(prefix, first) => format!("{prefix}:{value}"),
+        _ => format!("{prefix}{value}"),
     }
 }
 
+/// Recover the value from a term built by [`boolean_term`] with `prefix`
+pub(crate) fn strip_boolean_prefix<'a>(term: &'a [u8], prefix: &str) -> Option<&'a [u8]> {
+    let value = term.strip_prefix(prefix.as_bytes())?;
+    Some(match value.strip_prefix(b":") {
+        Some(rest) if prefix_needs_colon(prefix, ':') => rest,
+        _ => value,
+    })
+}
+
+fn prefix_needs_colon(prefix: &str, first: char) -> bool {
+    (first.is_ascii_uppercase() || first == ':') && prefix.len() > 1 && !prefix.ends_with(':')
+}
+
 impl AsRef<str> for Term {
     fn as_ref(&self) -> &str {
         self.value.to_str().unwrap()
     }
 }
 
-impl AsRef<CxxString> for Term {
-    fn as_ref(&self) -> &CxxString {
-        &self.value
+impl AsRef<[u8]> for Term {
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_bytes()
     }
 }
 
-impl AsRef<ffi::TermIterator> for Term {
-    fn as_ref(&self) -> &ffi::TermIterator {
-        &self.ptr
+impl AsRef<CxxString> for Term {
+    fn as_ref(&self) -> &CxxString {
+        &self.value
     }
 }
 
@@ -348,14 +446,19 @@ impl PartialOrd for Term {
 }
 
 /// An instance of a Xapian TermGenerator, which can be used to index text with optional stemming
-pub struct TermGenerator(Pin<Box<ffi::TermGenerator>>);
+pub struct TermGenerator {
+    // Xapian only borrows the stopper, so it must outlive (and therefore be dropped after) the
+    // underlying TermGenerator
+    ptr: Pin<Box<ffi::TermGenerator>>,
+    stopper: Option<StopperObj>,
+}
 
 impl TermGenerator {
     /// Increase the term position used when indexing index_text
     ///
     /// Useful to keep phrases from spanning logically separate regions of text
     pub fn increase_termpos(&mut self, delta: impl Into<Option<u32>>) {
-        self.0
+        self.ptr
             .as_mut()
             .increase_termpos(delta.into().unwrap_or(100).into());
     }
@@ -369,52 +472,63 @@ impl TermGenerator {
         text: impl AsRef<str>,
         increment: impl Into<Option<u32>>,
         prefix: impl Into<Option<T>>,
-    ) where
+    ) -> crate::Result<()>
+    where
         T: AsRef<str> + Default,
     {
         cxx::let_cxx_string!(text = text.as_ref());
         cxx::let_cxx_string!(prefix = prefix.into().unwrap_or_default().as_ref());
-        self.0
-            .as_mut()
-            .index_text1(&text, increment.into().unwrap_or(1).into(), &prefix)
+        Ok(fallible::term_generator_index_text(
+            self.ptr.as_mut(),
+            &text,
+            increment.into().unwrap_or(1),
+            &prefix,
+        )?)
     }
 
     /// Set the currently active database
     pub fn set_database(&mut self, db: impl AsRef<ffi::WritableDatabase>) {
-        self.0.as_mut().set_database(db.as_ref())
+        self.ptr.as_mut().set_database(db.as_ref())
     }
 
     /// Set the currently active document
     pub fn set_document(&mut self, doc: impl AsRef<ffi::Document>) {
-        self.0.as_mut().set_document(doc.as_ref())
+        self.ptr.as_mut().set_document(doc.as_ref())
     }
 
     /// Set the stemmer to be used when indexing text
     pub fn set_stemmer(&mut self, stem: impl AsRef<ffi::Stem>) {
-        self.0.as_mut().set_stemmer(stem.as_ref())
+        self.ptr.as_mut().set_stemmer(stem.as_ref())
     }
 
     /// Set the stemming strategy to be used when indexing text
     pub fn set_stemming_strategy(&mut self, strategy: impl Into<ffi::TermGenerator_stem_strategy>) {
-        self.0.as_mut().set_stemming_strategy(strategy.into())
+        self.ptr.as_mut().set_stemming_strategy(strategy.into())
     }
 
     /// Set the stopper to be used when indexing text
+    ///
+    /// The stopper is owned by this `TermGenerator`, replacing (and dropping) any previous stopper
     pub fn set_stopper<T: crate::Stopper + 'static>(&mut self, stopper: impl Into<Option<T>>) {
-        let stopper = stopper.into().map_or(std::ptr::null(), |s| {
-            Deref::deref(&s.into_ffi().upcast()) as *const _
-        });
-        unsafe { ffi::shim::term_generator_set_stopper(self.0.as_mut(), stopper) }
+        let stopper = stopper.into().map(|s| s.into_ffi());
+        let stopper_ptr = stopper
+            .as_ref()
+            .map_or(std::ptr::null(), |s| Deref::deref(&s.upcast()) as *const _);
+        unsafe { ffi::shim::term_generator_set_stopper(self.ptr.as_mut(), stopper_ptr) }
+        self.stopper = stopper;
     }
 
     /// Set the stopper strategy to be used when indexing text
     pub fn set_stopper_strategy(&mut self, strategy: impl Into<ffi::TermGenerator_stop_strategy>) {
-        self.0.as_mut().set_stopper_strategy(strategy.into())
+        self.ptr.as_mut().set_stopper_strategy(strategy.into())
     }
 }
 
 impl Default for TermGenerator {
     fn default() -> Self {
-        Self(ffi::TermGenerator::new2().within_box())
+        Self {
+            ptr: ffi::TermGenerator::new2().within_box(),
+            stopper: None,
+        }
     }
 }
@@ -0,0 +1,62 @@
+use std::ops::Deref;
+
+/// A transaction in progress on a [`WritableDatabase`][crate::WritableDatabase], created by
+/// [`WritableDatabase::begin`][crate::WritableDatabase::begin]
+///
+/// The transaction's changes are applied by [`Transaction::commit`]. If the guard is dropped
+/// without being committed, including while unwinding from a panic, the transaction is cancelled
+/// and its changes are discarded
+///
+/// The guard dereferences to the database for reading, and has the same methods to modify it as
+/// the database itself, but none which begin or end a transaction, so that the transaction can
+/// only be ended through the guard
+pub struct Transaction<'db> {
+    db: &'db mut crate::WritableDatabase,
+    done: bool,
+}
+
+impl<'db> Transaction<'db> {
+    pub(crate) fn new(db: &'db mut crate::WritableDatabase, flushed: bool) -> crate::Result<Self> {
+        db.begin_transaction(flushed)?;
+        Ok(Self { db, done: false })
+    }
+
+    /// Discard the changes made during the transaction
+    pub fn cancel(mut self) -> crate::Result<()> {
+        self.done = true;
+        self.db.cancel_transaction()
+    }
+
+    /// Apply the changes made during the transaction
+    pub fn commit(mut self) -> crate::Result<()> {
+        self.done = true;
+        self.db.commit_transaction()
+    }
+
+    // The database modified by the methods the guard shares with it
+    pub(crate) fn database(&self) -> &crate::WritableDatabase {
+        self.db
+    }
+
+    pub(crate) fn database_mut(&mut self) -> &mut crate::WritableDatabase {
+        self.db
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = crate::WritableDatabase;
+
+    fn deref(&self) -> &Self::Target {
+        self.db
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            // There is no way to report a failure from here, and the database will refuse to
+            // start another transaction if this one is somehow still open
+            let _ = self.db.cancel_transaction();
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use xapian_rs::{BulkIndexer, Document, WritableDatabase};
+
+fn document(id: u32, text: &str) -> Document {
+    let mut doc = Document::default();
+    doc.set_data(text);
+    doc.add_boolean_term(format!("Q:{id}")).unwrap();
+    doc.add_boolean_term(format!("XPARITY:{}", id % 2)).unwrap();
+    doc
+}
+
+#[test]
+fn upsert_and_delete() {
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = BulkIndexer::new(&mut db).commit_interval(20);
+
+    for id in 0..50 {
+        indexer
+            .upsert(format!("Q:{id}"), &document(id, "original"))
+            .unwrap();
+    }
+    assert_eq!(indexer.stats().commits(), 2);
+
+    for id in 0..10 {
+        indexer
+            .upsert(format!("Q:{id}"), &document(id, "updated"))
+            .unwrap();
+    }
+    let doc = indexer.add(&document(50, "added")).unwrap();
+    assert!(indexer.delete(doc).unwrap());
+    assert!(!indexer.delete(doc).unwrap());
+    assert_eq!(indexer.delete_by_term("XPARITY:1").unwrap(), 25);
+
+    let stats = indexer.finish().unwrap();
+    assert_eq!(stats.added(), 51);
+    assert_eq!(stats.replaced(), 10);
+    assert_eq!(stats.deleted(), 26);
+    assert_eq!(stats.commits(), 4);
+    assert!(stats.bytes() > 0);
+
+    assert_eq!(db.doc_count().unwrap(), 25);
+    assert_eq!(db.termfreq("XPARITY:1").unwrap(), 0);
+}
+
+#[test]
+fn commit_on_batch_size() {
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = BulkIndexer::new(&mut db).max_batch_bytes(1);
+
+    indexer.add(&document(1, "first")).unwrap();
+    indexer.add(&document(2, "second")).unwrap();
+    assert_eq!(indexer.stats().commits(), 2);
+
+    let stats = indexer.finish().unwrap();
+    assert_eq!((stats.added(), stats.commits()), (2, 2));
+}
+
+#[test]
+fn upsert_counts_duplicates_as_deleted() {
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = BulkIndexer::new(&mut db);
+
+    for id in 0..3 {
+        indexer.add(&document(id, "duplicate")).unwrap();
+    }
+    indexer
+        .upsert("XPARITY:0", &document(10, "merged"))
+        .unwrap();
+
+    let stats = indexer.finish().unwrap();
+    assert_eq!(stats.added(), 3);
+    assert_eq!(stats.replaced(), 1);
+    assert_eq!(stats.deleted(), 1);
+    assert_eq!(db.doc_count().unwrap(), 2);
+}
@@ -0,0 +1,35 @@
+use xapian_rs::{CheckFlags, Database};
+
+mod common;
+
+#[test]
+fn check_database() {
+    let path = common::scratch("check");
+    let mut db = common::seed_objects(path.clone());
+    db.commit().unwrap();
+
+    let mut output = Vec::new();
+    let report = Database::check(&path, CheckFlags::SHOW_STATS, &mut output).unwrap();
+    assert!(report.is_ok());
+    assert_eq!(report.errors(), 0);
+    assert!(!output.is_empty());
+    assert!(report.tables().iter().any(|t| t.name() == "postlist"));
+    assert!(report.tables().iter().all(|t| t.is_ok()));
+    assert_eq!(report.failed_tables().count(), 0);
+}
+
+#[test]
+fn check_table() {
+    let path = common::scratch("check-table");
+    let mut db = common::seed_states(path.clone());
+    db.commit().unwrap();
+
+    let report = Database::check(path.join("postlist.glass"), None, std::io::sink()).unwrap();
+    assert!(report.is_ok());
+}
+
+#[test]
+fn check_missing_database() {
+    let path = common::scratch("check-missing");
+    assert!(Database::check(&path, None, std::io::sink()).is_err());
+}
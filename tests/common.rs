@@ -54,24 +54,34 @@ pub fn us_states() -> Vec<StateRecord> {
     parse(include_bytes!("data/states.csv"))
 }
 
+/// Get a path for an on-disk test database, removing anything left there by a previous run
+pub fn scratch(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&path);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
 pub fn seed_objects(path: impl Into<Option<PathBuf>>) -> WritableDatabase {
     let mut db = path.into().map_or_else(WritableDatabase::inmemory, |path| {
-        WritableDatabase::open(path, None, None, None, None)
+        WritableDatabase::open(path, None, None, None, None).expect("Failed to open database")
     });
 
     let mut indexer = TermGenerator::default();
-    indexer.set_stemmer(Stem::for_language("english"));
+    indexer.set_stemmer(Stem::for_language("english").unwrap());
 
     for item in museum_objects() {
         let mut doc = Document::default();
         indexer.set_document(&doc);
 
-        indexer.index_text(&item.title, None, "S:");
-        indexer.index_text(&item.description, None, "XD:");
+        indexer.index_text(&item.title, None, "S:").unwrap();
+        indexer.index_text(&item.description, None, "XD:").unwrap();
 
-        indexer.index_text::<&str>(&item.title, None, None);
+        indexer.index_text::<&str>(&item.title, None, None).unwrap();
         indexer.increase_termpos(None);
-        indexer.index_text::<&str>(&item.description, None, None);
+        indexer
+            .index_text::<&str>(&item.description, None, None)
+            .unwrap();
 
         let data = serde_json::to_string(&item).unwrap();
         doc.set_data(&data);
@@ -88,7 +98,7 @@ pub fn seed_objects(path: impl Into<Option<PathBuf>>) -> WritableDatabase {
             })
             .flatten()
         {
-            doc.set_value(0, largest_measurement);
+            doc.set_value(0, largest_measurement).unwrap();
         }
 
         if let Some(year) = item
@@ -97,13 +107,14 @@ pub fn seed_objects(path: impl Into<Option<PathBuf>>) -> WritableDatabase {
             .filter_map(|s| s.parse::<i32>().ok())
             .next()
         {
-            doc.set_value(1, year)
+            doc.set_value(1, year).unwrap();
         }
 
         let idterm = format!("Q:{}", &item.id_number);
-        doc.add_boolean_term(&idterm);
+        doc.add_boolean_term(&idterm).unwrap();
 
-        db.replace_document_by_term(&idterm, &doc);
+        db.replace_document_by_term(&idterm, &doc)
+            .expect("Failed to index document");
     }
 
     db
@@ -111,35 +122,40 @@ pub fn seed_objects(path: impl Into<Option<PathBuf>>) -> WritableDatabase {
 
 pub fn seed_states(path: impl Into<Option<PathBuf>>) -> WritableDatabase {
     let mut db = path.into().map_or_else(WritableDatabase::inmemory, |path| {
-        WritableDatabase::open(path, None, None, None, None)
+        WritableDatabase::open(path, None, None, None, None).expect("Failed to open database")
     });
 
     let mut indexer = TermGenerator::default();
-    indexer.set_stemmer(Stem::for_language("english"));
+    indexer.set_stemmer(Stem::for_language("english").unwrap());
 
     for item in us_states() {
         let mut doc = Document::default();
         indexer.set_document(&doc);
 
-        indexer.index_text(&item.name, None, "S:");
-        indexer.index_text(&item.description, None, "XD:");
-        indexer.index_text(&item.motto, None, "XM:");
+        indexer.index_text(&item.name, None, "S:").unwrap();
+        indexer.index_text(&item.description, None, "XD:").unwrap();
+        indexer.index_text(&item.motto, None, "XM:").unwrap();
 
-        indexer.index_text::<&str>(&item.name, None, None);
+        indexer.index_text::<&str>(&item.name, None, None).unwrap();
         indexer.increase_termpos(None);
-        indexer.index_text::<&str>(&item.description, None, None);
+        indexer
+            .index_text::<&str>(&item.description, None, None)
+            .unwrap();
         indexer.increase_termpos(None);
-        indexer.index_text::<&str>(&item.motto, None, None);
+        indexer.index_text::<&str>(&item.motto, None, None).unwrap();
 
         let admission_year = item.admitted[0..4].parse::<u16>().unwrap();
-        doc.set_value(1, xapian_rs::ToValue::serialize(&admission_year));
-        doc.set_value(2, &item.admitted);
-        doc.set_value(3, xapian_rs::ToValue::serialize(&item.population));
+        doc.set_value(1, xapian_rs::ToValue::serialize(&admission_year))
+            .unwrap();
+        doc.set_value(2, &item.admitted).unwrap();
+        doc.set_value(3, xapian_rs::ToValue::serialize(&item.population))
+            .unwrap();
 
         let idterm = format!("Q:{}", &item.order);
-        doc.add_boolean_term(&idterm);
+        doc.add_boolean_term(&idterm).unwrap();
 
-        db.replace_document_by_term(&idterm, &doc);
+        db.replace_document_by_term(&idterm, &doc)
+            .expect("Failed to index document");
     }
 
     db
@@ -0,0 +1,72 @@
+use std::{cell::RefCell, rc::Rc};
+
+use bytes::Bytes;
+use xapian_rs::{CompactOptions, CompactionLevel, Database};
+
+mod common;
+
+#[test]
+fn compact_with_progress() {
+    let mut db = common::seed_objects(common::scratch("compact-src"));
+    db.commit().unwrap();
+
+    let tables = Rc::new(RefCell::new(Vec::new()));
+    let seen = tables.clone();
+    let options = CompactOptions::new()
+        .level(CompactionLevel::Fuller)
+        .block_size(4096)
+        .on_progress(move |table, _status| seen.borrow_mut().push(table.to_string()));
+
+    let dest = common::scratch("compact-dest");
+    db.compact(&dest, options).unwrap();
+    assert!(!tables.borrow().is_empty());
+
+    let compacted = Database::open(&dest, None).unwrap();
+    assert_eq!(compacted.doc_count().unwrap(), 100);
+}
+
+#[test]
+fn compact_single_file() {
+    let mut db = common::seed_states(common::scratch("compact-single-src"));
+    db.commit().unwrap();
+
+    let dest = common::scratch("compact-single-dest");
+    db.compact(&dest, CompactOptions::new().single_file(true))
+        .unwrap();
+    assert!(dest.is_file());
+    assert_eq!(
+        Database::open(&dest, None).unwrap().doc_count().unwrap(),
+        50
+    );
+}
+
+#[test]
+fn merge_shards() {
+    let mut museum = common::seed_objects(common::scratch("compact-museum"));
+    museum.set_metadata("source", "museum").unwrap();
+    museum.commit().unwrap();
+
+    let mut states = common::seed_states(common::scratch("compact-states"));
+    states.set_metadata("source", "states").unwrap();
+    states.commit().unwrap();
+
+    let shards = Database::from_shards([&museum, &states]).unwrap();
+    let options = CompactOptions::new()
+        .multipass(true)
+        .on_duplicate_metadata(|key, values| {
+            assert_eq!(key, "source");
+            let joined = values
+                .iter()
+                .map(|v| String::from_utf8_lossy(v).into_owned())
+                .collect::<Vec<_>>()
+                .join(",");
+            Bytes::from(joined)
+        });
+
+    let dest = common::scratch("compact-merged");
+    shards.compact(&dest, options).unwrap();
+
+    let merged = Database::open(&dest, None).unwrap();
+    assert_eq!(merged.doc_count().unwrap(), 150);
+    assert_eq!(&merged.metadata("source").unwrap()[..], b"museum,states");
+}
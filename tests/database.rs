@@ -0,0 +1,316 @@
+use xapian_rs::{
+    DocId, Document, Enquire, Error, FromValue, PostingStats, Query, WritableDatabase,
+};
+
+mod common;
+
+fn docid(id: u32) -> DocId {
+    DocId::new(id).unwrap()
+}
+
+#[test]
+fn lookup_by_docid() {
+    let mut db = common::seed_objects(None);
+    assert_eq!(db.last_docid().unwrap(), Some(docid(100)));
+
+    assert!(db.has_document(docid(1)).unwrap());
+    assert!(!db.has_document(docid(1000)).unwrap());
+
+    let doc = db.document(docid(1)).unwrap();
+    assert_eq!(doc.id(), Some(docid(1)));
+    assert!(!doc.data().unwrap().is_empty());
+
+    let err = db.document(docid(1000)).unwrap_err();
+    assert!(matches!(err, Error::DocNotFound(_)), "{err}");
+
+    let docs = db.documents([docid(1), docid(2), docid(3)]).unwrap();
+    let ids = docs.iter().map(|d| d.id()).collect::<Vec<_>>();
+    assert_eq!(ids, [Some(docid(1)), Some(docid(2)), Some(docid(3))]);
+
+    db.delete_document(docid(2)).unwrap();
+    assert!(!db.has_document(docid(2)).unwrap());
+    assert!(db.documents([docid(1), docid(2)]).is_err());
+
+    let ro = db.read_only();
+    assert_eq!(ro.last_docid().unwrap(), Some(docid(100)));
+    assert!(ro.has_document(docid(3)).unwrap());
+    assert_eq!(ro.document(docid(3)).unwrap().id(), Some(docid(3)));
+}
+
+#[test]
+fn empty_database_has_no_last_docid() {
+    let db = WritableDatabase::inmemory();
+    assert_eq!(db.last_docid().unwrap(), None);
+}
+
+#[test]
+fn allterms_with_prefix() {
+    let db = common::seed_objects(None);
+
+    let ids = db
+        .allterms("Q:")
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(ids.len(), 100);
+    for term in &ids {
+        assert!(AsRef::<str>::as_ref(term).starts_with("Q:"));
+        assert_eq!(term.frequency().unwrap(), 1);
+        assert_eq!(term.collection_frequency().unwrap(), Some(1));
+    }
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+    assert!(db.allterms("").unwrap().count() > ids.len());
+    assert_eq!(db.allterms("no-such-prefix").unwrap().count(), 0);
+
+    let ro = db.read_only();
+    assert_eq!(ro.allterms("Q:").unwrap().count(), 100);
+
+    let doc = db.document(docid(1)).unwrap();
+    let term = doc.terms().unwrap().next().unwrap().unwrap();
+    assert_eq!(term.collection_frequency().unwrap(), None);
+}
+
+#[test]
+fn allterms_frequencies_outlive_iteration() {
+    let db = common::seed_objects(None);
+
+    let terms = db
+        .allterms("S:")
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(terms.iter().any(|t| t.frequency().unwrap() > 1));
+    for term in &terms {
+        let fresh = db
+            .allterms(term.to_string())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(term.frequency().unwrap(), fresh.frequency().unwrap());
+    }
+}
+
+#[test]
+fn postings() {
+    let db = common::seed_objects(None);
+
+    let all = db
+        .postings("")
+        .unwrap()
+        .with_stats(PostingStats::DOCLENGTH | PostingStats::UNIQUE_TERMS)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(all.len(), 100);
+    assert_eq!(all[0].docid(), docid(1));
+    assert_eq!(all[99].docid(), docid(100));
+    for posting in &all {
+        assert!(posting.doclength().unwrap() > 0);
+        assert!(posting.unique_terms().unwrap() > 0);
+        assert!(matches!(posting.wdf(), Err(Error::InvalidOperation(_))));
+    }
+
+    let term = db.allterms("S:").unwrap().next().unwrap().unwrap();
+    let term: &str = term.as_ref();
+    let postings = db
+        .postings(term)
+        .unwrap()
+        .with_stats(PostingStats::all())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        postings.len() as u32,
+        db.allterms(term)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .frequency()
+            .unwrap()
+    );
+    for posting in &postings {
+        let wdf = posting.wdf().unwrap();
+        assert!(wdf > 0);
+        assert_eq!(
+            posting.positions().unwrap().count() as u32,
+            posting.positions_len().unwrap()
+        );
+        assert!(posting.doclength().unwrap() >= wdf);
+    }
+
+    // The all-documents posting list has no positions, even when they are requested
+    let all = db
+        .postings("")
+        .unwrap()
+        .with_stats(PostingStats::POSITIONS)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(matches!(
+        all[0].positions(),
+        Err(Error::InvalidOperation(_))
+    ));
+
+    let mut iter = db.postings("").unwrap();
+    iter.skip_to(docid(50)).unwrap();
+    assert_eq!(iter.next().map(|p| p.unwrap().docid()), Some(docid(50)));
+    assert_eq!(iter.next().map(|p| p.unwrap().docid()), Some(docid(51)));
+    iter.skip_to(docid(1000)).unwrap();
+    assert!(iter.next().is_none());
+
+    assert_eq!(db.postings("no-such-term").unwrap().count(), 0);
+}
+
+#[test]
+fn postings_outlive_iteration() {
+    let mut db = xapian_rs::WritableDatabase::inmemory();
+    for positions in [&[1u32, 4][..], &[2]] {
+        let mut doc = Document::default();
+        for &pos in positions {
+            doc.add_posting("fox", pos.into(), None).unwrap();
+        }
+        db.add_document(&doc).unwrap();
+    }
+
+    let postings = db
+        .postings("fox")
+        .unwrap()
+        .with_stats(PostingStats::WDF | PostingStats::POSITIONS)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into_iter()
+        .map(|p| {
+            let positions = p.positions().unwrap().map(u32::from).collect::<Vec<_>>();
+            (u32::from(p.docid()), p.wdf().unwrap(), positions)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(postings, [(1, 2, vec![1, 4]), (2, 1, vec![2])]);
+}
+
+#[test]
+fn value_streams() {
+    let db = common::seed_states(None);
+    assert_eq!(db.value_freq(1).unwrap(), 50);
+    assert_eq!(db.value_freq(9).unwrap(), 0);
+
+    let years = db
+        .values_as::<u16>(1)
+        .unwrap()
+        .map(|value| value.unwrap().1.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(years.len(), 50);
+
+    let min = *years.iter().min().unwrap();
+    let max = *years.iter().max().unwrap();
+    assert_eq!(min, 1787);
+    assert_eq!(max, 1959);
+
+    let lower = <u16 as FromValue>::deserialize(db.value_lower_bound(1).unwrap()).unwrap();
+    let upper = <u16 as FromValue>::deserialize(db.value_upper_bound(1).unwrap()).unwrap();
+    assert!(lower <= min);
+    assert!(upper >= max);
+    assert!(db.value_lower_bound(9).unwrap().is_empty());
+
+    let mut values = db.values(2).unwrap();
+    let (first, admitted) = values.next().unwrap().unwrap();
+    assert_eq!(first, docid(1));
+    assert_eq!(admitted.len(), 8);
+
+    values.skip_to(docid(25)).unwrap();
+    assert_eq!(values.next().map(|v| v.unwrap().0), Some(docid(25)));
+
+    let mut years = db.values_as::<u16>(1).unwrap();
+    years.skip_to(docid(50)).unwrap();
+    assert_eq!(years.next().map(|v| v.unwrap().0), Some(docid(50)));
+    assert!(years.next().is_none());
+}
+
+#[test]
+fn statistics() {
+    let db = common::seed_objects(None);
+
+    let total_length = db.total_length().unwrap();
+    let doclengths = (1..=100)
+        .map(|id| db.doclength(docid(id)).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        doclengths.iter().map(|&l| l as u64).sum::<u64>(),
+        total_length
+    );
+    assert!((db.avlength().unwrap() - total_length as f64 / 100.0).abs() < 1e-6);
+
+    let shortest = *doclengths.iter().min().unwrap();
+    let longest = *doclengths.iter().max().unwrap();
+    assert!(db.doclength_lower_bound().unwrap() <= shortest);
+    assert!(db.doclength_upper_bound().unwrap() >= longest);
+
+    assert!(db.unique_terms(docid(1)).unwrap() > 0);
+    assert!(db.unique_terms(docid(1)).unwrap() <= db.doclength(docid(1)).unwrap());
+    assert!(db.doclength(docid(1000)).is_err());
+    assert!(db.has_positions().unwrap());
+
+    let term = db.allterms("XD:").unwrap().next().unwrap().unwrap();
+    let term: &str = term.as_ref();
+    let postings = db
+        .postings(term)
+        .unwrap()
+        .with_stats(PostingStats::WDF)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(db.termfreq(term).unwrap() as usize, postings.len());
+    let wdfs = postings
+        .iter()
+        .map(|p| p.wdf().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(db.collection_freq(term).unwrap(), wdfs.iter().sum::<u32>());
+    assert!(db.wdf_upper_bound(term).unwrap() >= *wdfs.iter().max().unwrap());
+
+    assert_eq!(db.termfreq("no-such-term").unwrap(), 0);
+    assert_eq!(db.collection_freq("no-such-term").unwrap(), 0);
+
+    let mut enquire = Enquire::new(&db);
+    enquire.set_query(Query::term(term, None, None), None);
+    let mset = enquire.mset(0, 10, None, None).unwrap();
+    assert_eq!(mset.termfreq(term).unwrap(), db.termfreq(term).unwrap());
+
+    let ro = db.read_only();
+    assert_eq!(ro.total_length().unwrap(), total_length);
+    assert_eq!(ro.doclength(docid(1)).unwrap(), doclengths[0]);
+}
+
+#[test]
+fn metadata() {
+    let mut db = WritableDatabase::inmemory();
+    assert!(db.metadata("schema_version").unwrap().is_empty());
+    assert!(db.metadata_as::<u32>("schema_version").unwrap().is_none());
+
+    db.set_metadata("schema_version", 3u32).unwrap();
+    db.set_metadata("checkpoint:museum", "offset-42").unwrap();
+    db.set_metadata("checkpoint:states", "offset-7").unwrap();
+
+    assert_eq!(db.metadata_as::<u32>("schema_version").unwrap(), Some(3));
+    assert!(matches!(
+        db.metadata_as::<bool>("checkpoint:museum"),
+        Err(Error::Serialisation(_))
+    ));
+    assert_eq!(&db.metadata("checkpoint:museum").unwrap()[..], b"offset-42");
+
+    let keys = db
+        .metadata_keys("checkpoint:")
+        .unwrap()
+        .map(|k| k.unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["checkpoint:museum", "checkpoint:states"]);
+    assert_eq!(db.metadata_keys("").unwrap().count(), 3);
+
+    db.set_metadata("checkpoint:museum", "").unwrap();
+    assert!(db.metadata("checkpoint:museum").unwrap().is_empty());
+    assert_eq!(db.metadata_keys("checkpoint:").unwrap().count(), 1);
+
+    let ro = db.read_only();
+    assert_eq!(
+        ro.metadata_as::<String>("checkpoint:states").unwrap(),
+        Some(String::from("offset-7"))
+    );
+    assert_eq!(ro.metadata_keys("schema").unwrap().count(), 1);
+}
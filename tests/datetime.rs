@@ -0,0 +1,134 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use xapian_rs::{
+    DateTimeRangeProcessor, Document, Enquire, Epoch, EpochDateRangeProcessor, QueryParser,
+    WritableDatabase,
+};
+
+fn timestamp() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 31, 12, 30, 15).unwrap()
+}
+
+#[test]
+fn naive_round_trip() {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+    let datetime = timestamp().naive_utc();
+
+    let mut doc = Document::default();
+    doc.set_value(0, date).unwrap();
+    doc.set_value(1, datetime).unwrap();
+    doc.set_value(2, Epoch(date)).unwrap();
+    doc.set_value(3, Epoch(datetime)).unwrap();
+
+    assert_eq!(
+        doc.value::<String>(0).unwrap(),
+        Some(Ok("20240131".to_string()))
+    );
+    assert_eq!(doc.value::<NaiveDate>(0).unwrap(), Some(Ok(date)));
+    assert_eq!(doc.value::<NaiveDateTime>(1).unwrap(), Some(Ok(datetime)));
+    assert_eq!(
+        doc.value::<Epoch<NaiveDate>>(2).unwrap(),
+        Some(Ok(Epoch(date)))
+    );
+    assert_eq!(
+        doc.value::<Epoch<NaiveDateTime>>(3).unwrap(),
+        Some(Ok(Epoch(datetime)))
+    );
+    assert_eq!(doc.value::<f64>(3).unwrap(), Some(Ok(1706704215.0)));
+}
+
+#[test]
+fn offsets_normalised_to_utc() {
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let local = timestamp().with_timezone(&offset);
+
+    let mut doc = Document::default();
+    doc.set_value(0, local).unwrap();
+    doc.set_value(1, Epoch(local)).unwrap();
+
+    assert_eq!(
+        doc.value::<String>(0).unwrap(),
+        Some(Ok("20240131123015".to_string()))
+    );
+    assert_eq!(
+        doc.value::<DateTime<Utc>>(0).unwrap(),
+        Some(Ok(timestamp()))
+    );
+    let restored = doc
+        .value::<DateTime<FixedOffset>>(0)
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored, local);
+    assert_eq!(restored.offset().local_minus_utc(), 0);
+    assert_eq!(
+        doc.value::<Epoch<DateTime<Utc>>>(1).unwrap(),
+        Some(Ok(Epoch(timestamp())))
+    );
+}
+
+#[test]
+fn epoch_keeps_fractional_seconds() {
+    let precise = timestamp() + chrono::Duration::milliseconds(250);
+    let mut doc = Document::default();
+    doc.set_value(0, Epoch(precise)).unwrap();
+    assert_eq!(
+        doc.value::<Epoch<DateTime<Utc>>>(0).unwrap(),
+        Some(Ok(Epoch(precise)))
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn offset_date_time() {
+    use time::{macros::datetime, OffsetDateTime};
+
+    let local = datetime!(2024-01-31 14:30:15 +2);
+    let mut doc = Document::default();
+    doc.set_value(0, local).unwrap();
+    doc.set_value(1, Epoch(local)).unwrap();
+
+    // Encoded identically to the equivalent chrono timestamp
+    assert_eq!(
+        doc.value::<DateTime<Utc>>(0).unwrap(),
+        Some(Ok(timestamp()))
+    );
+    assert_eq!(doc.value::<OffsetDateTime>(0).unwrap(), Some(Ok(local)));
+    assert_eq!(
+        doc.value::<Epoch<OffsetDateTime>>(1).unwrap(),
+        Some(Ok(Epoch(local)))
+    );
+}
+
+#[test]
+fn range_processors() {
+    let mut db = WritableDatabase::inmemory();
+    for day in [1, 15, 28] {
+        let date = NaiveDate::from_ymd_opt(2024, 2, day).unwrap();
+        let mut doc = Document::default();
+        doc.set_value(0, date.and_hms_opt(9, 0, 0).unwrap().and_utc())
+            .unwrap();
+        doc.set_value(1, Epoch(date)).unwrap();
+        db.add_document(&doc).unwrap();
+    }
+    db.commit().unwrap();
+
+    let mut qp = QueryParser::default();
+    qp.add_rangeprocessor("at:", 0, DateTimeRangeProcessor, false, false, None);
+    qp.add_rangeprocessor("on:", 1, EpochDateRangeProcessor, false, false, None);
+
+    let mut count = |query: &str| {
+        let query = qp.parse_query::<&str>(query, None, None).unwrap();
+        let mut enquire = Enquire::new(&db);
+        enquire.set_query(&query, None);
+        enquire.mset(0, 10, 10, None).unwrap().matches().count()
+    };
+
+    assert_eq!(count("at:2024-02-01T00:00:00..2024-02-20T00:00:00"), 2);
+    assert_eq!(
+        count("at:2024-02-15T10:00:00+02:00..2024-02-15T12:00:00+02:00"),
+        1
+    );
+    assert_eq!(count("on:2024-02-15..2024-02-28"), 2);
+}
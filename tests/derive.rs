@@ -0,0 +1,107 @@
+#![cfg(feature = "derive")]
+
+use xapian_rs::{
+    DocId, Document, Error, FromDocument, IntoDocument, TermGenerator, WritableDatabase,
+    XapianDocument,
+};
+
+#[derive(Debug, Default, PartialEq, XapianDocument)]
+struct Book {
+    #[xapian(id, prefix = "Q:")]
+    isbn: String,
+    #[xapian(text, prefix = "S", weight = 2)]
+    title: String,
+    #[xapian(text)]
+    summary: String,
+    #[xapian(boolean, prefix = "XC")]
+    category: String,
+    #[xapian(value = 1)]
+    year: u16,
+    #[xapian(data)]
+    notes: String,
+}
+
+fn dune() -> Book {
+    Book {
+        isbn: "9780441013593".into(),
+        title: "Dune".into(),
+        summary: "A desert planet and its spice".into(),
+        category: "fiction".into(),
+        year: 1965,
+        notes: "First of the series".into(),
+    }
+}
+
+#[test]
+fn into_document() {
+    let book = dune();
+    assert_eq!(book.unique_term().as_deref(), Some("Q:9780441013593"));
+
+    let mut indexer = TermGenerator::default();
+    let doc = book.into_document(&mut indexer).unwrap();
+
+    assert_eq!(doc.term("Sdune").unwrap().unwrap().wdf().unwrap(), 2);
+    assert!(doc.term("spice").unwrap().is_some());
+    assert!(doc.term("XCfiction").unwrap().is_some());
+    assert!(doc.term("Q:9780441013593").unwrap().is_some());
+    assert_eq!(doc.value::<u16>(1).unwrap(), Some(Ok(1965)));
+    assert_eq!(doc.data().unwrap(), "First of the series");
+}
+
+#[test]
+fn from_document() {
+    let book = dune();
+    let unique_term = book.unique_term().unwrap();
+
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = TermGenerator::default();
+    let doc = book.into_document(&mut indexer).unwrap();
+    let id = db.replace_document_by_term(&unique_term, &doc).unwrap();
+
+    let doc = db.document(DocId::new(id).unwrap()).unwrap();
+    let restored = Book::from_document(&doc).unwrap();
+    assert_eq!(
+        restored,
+        Book {
+            title: String::new(),
+            summary: String::new(),
+            ..dune()
+        }
+    );
+}
+
+#[test]
+fn uppercase_boolean() {
+    let book = Book {
+        isbn: "ISBN-9780441013593".into(),
+        category: "Fiction".into(),
+        ..dune()
+    };
+    assert_eq!(book.unique_term().as_deref(), Some("Q:ISBN-9780441013593"));
+
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = TermGenerator::default();
+    let doc = book.into_document(&mut indexer).unwrap();
+    assert!(doc.term("XC:Fiction").unwrap().is_some());
+    let id = db.add_document(&doc).unwrap();
+
+    let doc = db.document(id).unwrap();
+    let restored = Book::from_document(&doc).unwrap();
+    assert_eq!(restored.category, "Fiction");
+    assert_eq!(restored.isbn, "ISBN-9780441013593");
+}
+
+#[test]
+fn non_utf8_terms() {
+    let mut indexer = TermGenerator::default();
+    let mut doc = dune().into_document(&mut indexer).unwrap();
+    doc.add_boolean_term(b"A\xff").unwrap();
+    assert_eq!(Book::from_document(&doc).unwrap().category, "fiction");
+
+    let mut doc = Document::default();
+    doc.add_boolean_term(b"XC\xff").unwrap();
+    assert!(matches!(
+        Book::from_document(&doc),
+        Err(Error::Serialisation(_))
+    ));
+}
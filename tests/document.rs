@@ -0,0 +1,147 @@
+use xapian_rs::{Document, Error};
+
+#[test]
+fn values() {
+    let mut doc = Document::default();
+    doc.set_value(3, 30u32).unwrap();
+    doc.set_value(1, 10u32).unwrap();
+    doc.set_value(2, 20u32).unwrap();
+    assert_eq!(doc.values_count().unwrap(), 3);
+
+    let slots = doc
+        .values()
+        .unwrap()
+        .map(|value| u32::from(value.unwrap().0))
+        .collect::<Vec<_>>();
+    assert_eq!(slots, [1, 2, 3]);
+
+    let values = doc
+        .values_as::<u32>()
+        .unwrap()
+        .map(|value| value.unwrap().1.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(values, [10, 20, 30]);
+
+    doc.remove_value(2).unwrap();
+    assert_eq!(doc.values_count().unwrap(), 2);
+    assert!(doc.value::<u32>(2).unwrap().is_none());
+    assert!(matches!(
+        doc.remove_value(2),
+        Err(Error::InvalidArgument(_))
+    ));
+
+    doc.clear_values();
+    assert_eq!(doc.values_count().unwrap(), 0);
+    assert_eq!(doc.values().unwrap().count(), 0);
+}
+
+#[test]
+fn postings() {
+    let mut doc = Document::default();
+    for (pos, word) in ["the", "quick", "fox", "and", "the", "lazy", "dog"]
+        .into_iter()
+        .enumerate()
+    {
+        doc.add_posting(word, (pos as u32 + 1).into(), None)
+            .unwrap();
+    }
+    doc.add_posting("XTITLE:fox", 1u32.into(), None).unwrap();
+    doc.add_posting("XTITLE:tale", 2u32.into(), None).unwrap();
+    assert_eq!(doc.termlist_count().unwrap(), 8);
+
+    let the = doc.term("the").unwrap().unwrap();
+    assert_eq!(the.wdf().unwrap(), 2);
+    let positions = the.positions().unwrap().map(u32::from).collect::<Vec<_>>();
+    assert_eq!(positions, [1, 5]);
+    assert!(doc.term("cat").unwrap().is_none());
+
+    // Terms keep their statistics after the iterator has moved on
+    let terms = doc.terms().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    let wdfs = terms
+        .iter()
+        .map(|t| (t.to_string(), t.wdf().unwrap(), t.positions_len().unwrap()))
+        .filter(|(_, wdf, _)| *wdf > 1)
+        .collect::<Vec<_>>();
+    assert_eq!(wdfs, [(String::from("the"), 2, 2)]);
+    assert!(matches!(
+        terms[0].frequency(),
+        Err(Error::InvalidOperation(_))
+    ));
+
+    doc.remove_posting("the", 5u32, None).unwrap();
+    assert_eq!(doc.term("the").unwrap().unwrap().wdf().unwrap(), 1);
+    assert!(matches!(
+        doc.remove_posting("the", 5u32, None),
+        Err(Error::InvalidArgument(_))
+    ));
+
+    // Removing every posting leaves the term with a wdf of 0, but still present
+    assert_eq!(
+        doc.remove_postings("XTITLE:fox", 1u32, 10u32, None)
+            .unwrap(),
+        1
+    );
+    assert_eq!(doc.term("XTITLE:fox").unwrap().unwrap().wdf().unwrap(), 0);
+    assert_eq!(
+        doc.remove_postings("XTITLE:tale", 5u32, 10u32, None)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn binary_terms() {
+    let mut doc = Document::default();
+    doc.add_boolean_term(b"XB\xff").unwrap();
+    doc.add_posting(b"XP\xfe", 1u32.into(), None).unwrap();
+    assert!(doc.term(b"XB\xff").unwrap().is_some());
+
+    doc.remove_posting(b"XP\xfe", 1u32, None).unwrap();
+    doc.remove_term(b"XB\xff").unwrap();
+    assert!(doc.term(b"XB\xff").unwrap().is_none());
+}
+
+fn sample() -> Document {
+    let mut doc = Document::default();
+    doc.set_data("a sample document");
+    doc.add_posting("sample", 1u32.into(), None).unwrap();
+    doc.add_boolean_term("Q:1").unwrap();
+    doc.set_value(0, 42u32).unwrap();
+    doc
+}
+
+fn assert_same(restored: &Document, original: &Document) {
+    assert_eq!(restored.data().unwrap(), original.data().unwrap());
+    assert_eq!(
+        restored.termlist_count().unwrap(),
+        original.termlist_count().unwrap()
+    );
+    assert_eq!(restored.value::<u32>(0).unwrap(), Some(Ok(42)));
+    let positions = restored
+        .term("sample")
+        .unwrap()
+        .unwrap()
+        .positions()
+        .unwrap()
+        .map(u32::from)
+        .collect::<Vec<_>>();
+    assert_eq!(positions, [1]);
+}
+
+#[test]
+fn serialisation() {
+    let doc = sample();
+    let restored = Document::from_bytes(doc.to_bytes().unwrap()).unwrap();
+    assert_same(&restored, &doc);
+
+    assert!(Document::from_bytes(b"not a document").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let doc = sample();
+    let json = serde_json::to_string(&doc).unwrap();
+    let restored: Document = serde_json::from_str(&json).unwrap();
+    assert_same(&restored, &doc);
+}
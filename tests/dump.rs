@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+use std::io::BufRead;
+
+use xapian_rs::{Blob, Database, Document, DumpRecord, WritableDatabase};
+
+mod common;
+
+#[test]
+fn dump_and_restore() {
+    let mut source = common::seed_states(common::scratch("dump-source"));
+    source.set_metadata("source", "states").unwrap();
+    source.add_spelling("mississippi", 3).unwrap();
+    source.add_synonym("ny", "new york").unwrap();
+    source.commit().unwrap();
+    let source = Database::from(source);
+
+    let mut dump = Vec::new();
+    source.dump(&mut dump).unwrap();
+
+    let records = dump
+        .lines()
+        .map(|line| serde_json::from_str::<DumpRecord>(&line.unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    let documents = records
+        .iter()
+        .filter(|r| matches!(r, DumpRecord::Document(_)))
+        .count();
+    assert_eq!(documents, 50);
+    assert!(records.contains(&DumpRecord::Spelling {
+        word: Blob::Text("mississippi".into()),
+        frequency: 3,
+    }));
+
+    let mut restored =
+        WritableDatabase::open(common::scratch("dump-restored"), None, None, None, None).unwrap();
+    restored.restore(dump.as_slice()).unwrap();
+    restored.commit().unwrap();
+
+    assert_eq!(restored.doc_count().unwrap(), 50);
+    assert_eq!(
+        restored.total_length().unwrap(),
+        source.total_length().unwrap()
+    );
+    assert_eq!(restored.metadata("source").unwrap(), "states");
+    assert_eq!(
+        restored.spelling_suggestion("missisippi", None).unwrap(),
+        Some("mississippi".to_string())
+    );
+
+    // Dumping the restored database reproduces the original dump exactly
+    let mut redump = Vec::new();
+    Database::from(&restored).dump(&mut redump).unwrap();
+    assert_eq!(redump, dump);
+}
+
+#[test]
+fn binary_terms_and_keys() {
+    let mut source =
+        WritableDatabase::open(common::scratch("dump-bin-src"), None, None, None, None).unwrap();
+    let mut doc = Document::default();
+    doc.add_posting(b"XB\xff", 1u32.into(), None).unwrap();
+    source.add_document(&doc).unwrap();
+    source.set_metadata(b"key\xfe", "binary key").unwrap();
+    source.add_spelling(b"w\xfeird", 2).unwrap();
+    source.add_synonym(b"t\xff", b"s\xff").unwrap();
+    source.commit().unwrap();
+
+    let mut dump = Vec::new();
+    Database::from(&source).dump(&mut dump).unwrap();
+    let records = dump
+        .lines()
+        .map(|line| serde_json::from_str::<DumpRecord>(&line.unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    assert!(records.contains(&DumpRecord::Synonym {
+        term: Blob::Binary(b"t\xff".to_vec()),
+        synonyms: vec![Blob::Binary(b"s\xff".to_vec())],
+    }));
+
+    let mut restored =
+        WritableDatabase::open(common::scratch("dump-bin-dest"), None, None, None, None).unwrap();
+    restored.restore(dump.as_slice()).unwrap();
+    restored.commit().unwrap();
+
+    assert_eq!(restored.termfreq(b"XB\xff").unwrap(), 1);
+    assert_eq!(restored.metadata(b"key\xfe").unwrap(), "binary key");
+    assert_eq!(restored.spellings().unwrap().count(), 1);
+    assert_eq!(restored.synonyms(b"t\xff").unwrap().count(), 1);
+}
+
+#[test]
+fn restore_rejects_malformed_lines() {
+    let mut db =
+        WritableDatabase::open(common::scratch("dump-malformed"), None, None, None, None).unwrap();
+    let result = db
+        .restore("{\"type\":\"spelling\",\"word\":\"ok\",\"frequency\":1}\nnot json\n".as_bytes());
+    assert!(matches!(result, Err(e) if e.message().starts_with("line 2")));
+}
@@ -0,0 +1,62 @@
+use xapian_rs::{Database, Document, Error, Stem, WritableDatabase};
+
+#[test]
+fn open_missing_database() {
+    let err = Database::open("tests/data/does-not-exist", None)
+        .err()
+        .expect("Opening a missing database should fail");
+    assert!(err.is_database_opening_error(), "{err}");
+    assert!(err.is_runtime_error());
+}
+
+#[test]
+fn remove_missing_term() {
+    let mut doc = Document::default();
+    doc.add_term("present", None).unwrap();
+
+    let err = doc.remove_term("absent").unwrap_err();
+    assert!(matches!(err, Error::InvalidArgument(_)), "{err}");
+    assert!(err.is_logic_error());
+    assert!(!err.message().is_empty());
+
+    doc.remove_term("present").unwrap();
+}
+
+#[test]
+fn unknown_stemmer() {
+    let err = Stem::for_language("klingon").err().unwrap();
+    assert_eq!(err.type_name(), "InvalidArgumentError");
+}
+
+#[test]
+fn replace_with_empty_term() {
+    let mut db = WritableDatabase::inmemory();
+    let err = db
+        .replace_document_by_term("", Document::default())
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidArgument(_)), "{err}");
+}
+
+struct BrokenPipe;
+
+impl std::io::Write for BrokenPipe {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Err(std::io::ErrorKind::BrokenPipe.into())
+    }
+}
+
+#[test]
+fn io_error_source() {
+    let err = Database::check("tests/data/does-not-exist", None, BrokenPipe).unwrap_err();
+    assert!(matches!(err, Error::Io(_)), "{err}");
+    assert_eq!(err.io_kind(), Some(std::io::ErrorKind::BrokenPipe));
+
+    let source = std::error::Error::source(&err).unwrap();
+    let io = source.downcast_ref::<std::io::Error>().unwrap();
+    assert_eq!(io.kind(), std::io::ErrorKind::BrokenPipe);
+    assert_eq!(err.clone(), err);
+}
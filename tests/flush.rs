@@ -0,0 +1,24 @@
+// Kept apart from the other tests, since setting a flush threshold modifies the environment
+use xapian_rs::{Document, Error, OpenOptions};
+
+mod common;
+
+#[test]
+fn flush_threshold() {
+    let path = common::scratch("open-flush");
+    // SAFETY: this is the only test in this binary, so nothing else touches the environment
+    let mut db = unsafe { OpenOptions::new().writable().flush_threshold(2) }
+        .open(&path)
+        .unwrap();
+    assert!(std::env::var_os("XAPIAN_FLUSH_THRESHOLD").is_none());
+
+    // Xapian commits by itself once the threshold is reached
+    for _ in 0..3 {
+        db.add_document(Document::default()).unwrap();
+    }
+    let reader = OpenOptions::new().open(&path).unwrap();
+    assert_eq!(reader.doc_count().unwrap(), 2);
+
+    let zero = unsafe { OpenOptions::new().writable().flush_threshold(0) }.open(&path);
+    assert!(matches!(zero, Err(Error::InvalidArgument(_))));
+}
@@ -0,0 +1,76 @@
+use xapian_rs::{CompactOptions, DbBackend, Document, Error, OpenOptions};
+
+mod common;
+
+#[test]
+fn create_then_open() {
+    let path = common::scratch("open-builder");
+    let mut db = OpenOptions::new()
+        .backend(DbBackend::Glass)
+        .writable()
+        .create_or_overwrite()
+        .no_sync()
+        .retry_lock()
+        .block_size(8192)
+        .open(&path)
+        .unwrap();
+    db.add_document(Document::default()).unwrap();
+    db.commit().unwrap();
+
+    let db = OpenOptions::new().open(&path).unwrap();
+    assert_eq!(db.doc_count().unwrap(), 1);
+}
+
+#[test]
+fn open_existing_requires_database() {
+    let path = common::scratch("open-missing");
+    let result = OpenOptions::new().writable().open_existing().open(&path);
+    assert!(result.is_err_and(|e| e.is_database_opening_error()));
+}
+
+#[test]
+fn open_single_file() {
+    let mut db = common::seed_states(common::scratch("open-single-src"));
+    db.commit().unwrap();
+    let path = common::scratch("open-single");
+    db.compact(&path, CompactOptions::new().single_file(true))
+        .unwrap();
+
+    let db = OpenOptions::new().open(&path).unwrap();
+    assert_eq!(db.doc_count().unwrap(), 50);
+
+    let file = std::fs::File::open(&path).unwrap();
+    let db = OpenOptions::new().open_file(file).unwrap();
+    assert_eq!(db.doc_count().unwrap(), 50);
+
+    let not_a_database = std::fs::File::open(file!()).unwrap();
+    assert!(OpenOptions::new().open_file(not_a_database).is_err());
+}
+
+#[test]
+fn conflicting_options() {
+    let path = common::scratch("open-conflicts");
+    let conflicts = [
+        OpenOptions::new()
+            .writable()
+            .no_sync()
+            .full_sync()
+            .open(&path),
+        OpenOptions::new()
+            .writable()
+            .open_existing()
+            .no_termlist()
+            .open(&path),
+        OpenOptions::new().writable().block_size(1000).open(&path),
+        OpenOptions::new()
+            .backend(DbBackend::InMemory)
+            .writable()
+            .block_size(8192)
+            .open(&path),
+    ];
+
+    for result in conflicts {
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+    assert!(!path.exists());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use std::rc::Rc;
+
+use xapian_rs::{Document, Enquire, MatchSpy, Query, QueryParser, TermGenerator};
+
+#[derive(Clone)]
+struct CountingSpy(Rc<()>);
+
+impl MatchSpy for CountingSpy {
+    fn observe(&self, _: &Document, _: f64) {}
+}
+
+#[test]
+fn match_decider_is_dropped_after_search() {
+    let db = common::seed_objects(None);
+    let token = Rc::new(());
+
+    let mut enquire = Enquire::new(&db);
+    enquire.set_query(Query::match_all(), None);
+
+    let held = Rc::clone(&token);
+    let decider = move |_: &Document| Rc::strong_count(&held) > 0;
+    let mset = enquire
+        .mset_with_decider(0, 10, None, None, decider)
+        .unwrap();
+    assert_eq!(mset.size(), 10);
+    assert_eq!(Rc::strong_count(&token), 1);
+}
+
+#[test]
+fn match_spies_are_owned_by_enquire() {
+    let db = common::seed_objects(None);
+    let spy = CountingSpy(Rc::new(()));
+
+    let mut enquire = Enquire::new(&db);
+    enquire.set_query(Query::match_all(), None);
+    enquire.add_matchspy(&spy);
+    enquire.add_matchspy(&spy);
+    assert_eq!(Rc::strong_count(&spy.0), 3);
+
+    enquire.mset(0, 10, None, None).unwrap();
+    enquire.clear_matchspies();
+    assert_eq!(Rc::strong_count(&spy.0), 1);
+
+    enquire.add_matchspy(&spy);
+    drop(enquire);
+    assert_eq!(Rc::strong_count(&spy.0), 1);
+}
+
+#[test]
+fn stoppers_and_processors_are_owned_by_parsers() {
+    let token = Rc::new(());
+
+    let mut qp = QueryParser::default();
+    let held = Rc::clone(&token);
+    qp.set_stopper(move |word: &str| Rc::strong_count(&held) > 0 && word == "the");
+    let held = Rc::clone(&token);
+    qp.add_custom_prefix("title", move |term: &str| {
+        let _ = &held;
+        Some(Query::term(term, None, None))
+    })
+    .unwrap();
+    let held = Rc::clone(&token);
+    qp.add_rangeprocessor(
+        "year:",
+        1,
+        move |start: &str, end: &str| {
+            let _ = &held;
+            (start.parse::<f64>().ok(), end.parse::<f64>().ok())
+        },
+        false,
+        false,
+        "year",
+    );
+    qp.parse_query::<&str>("the clock title:case year:1900..2000", None, None)
+        .unwrap();
+    assert_eq!(Rc::strong_count(&token), 4);
+
+    qp.set_stopper::<fn(&str) -> bool>(None);
+    assert_eq!(Rc::strong_count(&token), 3);
+    drop(qp);
+    assert_eq!(Rc::strong_count(&token), 1);
+
+    let mut indexer = TermGenerator::default();
+    let held = Rc::clone(&token);
+    indexer.set_stopper(move |_: &str| Rc::strong_count(&held) == 0);
+    assert_eq!(Rc::strong_count(&token), 2);
+    drop(indexer);
+    assert_eq!(Rc::strong_count(&token), 1);
+}
@@ -0,0 +1,97 @@
+use xapian_rs::{Database, DocId, Document, Enquire, Error, Query};
+
+mod common;
+
+#[test]
+fn reopen_sees_new_revision() {
+    let path = common::scratch("reopen");
+    let mut writer = common::seed_states(path.clone());
+    writer.commit().unwrap();
+
+    let mut reader = Database::open(&path, None).unwrap();
+    let revision = reader.revision().unwrap();
+    assert_eq!(reader.doc_count().unwrap(), 50);
+    assert_eq!(writer.revision().unwrap(), revision);
+
+    writer.add_document(Document::default()).unwrap();
+    writer.commit().unwrap();
+
+    assert_eq!(reader.doc_count().unwrap(), 50);
+    assert!(reader.reopen().unwrap());
+    assert_eq!(reader.doc_count().unwrap(), 51);
+    assert!(reader.revision().unwrap() > revision);
+    assert!(!reader.reopen().unwrap());
+}
+
+#[test]
+fn uuid() {
+    let path = common::scratch("reopen-uuid");
+    let mut writer = common::seed_states(path.clone());
+    writer.commit().unwrap();
+
+    let reader = Database::open(&path, None).unwrap();
+    let uuid = reader.uuid().unwrap();
+    assert!(uuid.as_ref().is_some_and(|u| !u.is_empty()));
+    assert_eq!(writer.uuid().unwrap(), uuid);
+}
+
+#[test]
+fn retry_on_modified() {
+    let mut db = Database::from(common::seed_states(None));
+
+    let mut attempts = 0;
+    let count = db
+        .retry_on_modified(None, |db| {
+            attempts += 1;
+            match attempts {
+                1 => Err(Error::DatabaseModified(Default::default())),
+                _ => db.doc_count(),
+            }
+        })
+        .unwrap();
+    assert_eq!((attempts, count), (2, 50));
+
+    let mut attempts = 0;
+    let result = db.retry_on_modified(2, |_| -> xapian_rs::Result<()> {
+        attempts += 1;
+        Err(Error::DatabaseModified(Default::default()))
+    });
+    assert!(matches!(result, Err(Error::DatabaseModified(_))));
+    assert_eq!(attempts, 3);
+
+    let mut attempts = 0;
+    let result = db.retry_on_modified(None, |db| {
+        attempts += 1;
+        db.document(DocId::new(1000u32).unwrap())
+    });
+    assert!(matches!(result, Err(Error::DocNotFound(_))));
+    assert_eq!(attempts, 1);
+}
+
+#[test]
+fn retry_search() {
+    let path = common::scratch("reopen-search");
+    let mut writer = common::seed_states(path.clone());
+    writer.commit().unwrap();
+
+    let mut reader = Database::open(&path, None).unwrap();
+    let mut enquire = Enquire::new(&reader);
+    enquire.set_query(&Query::match_all(), None);
+
+    // The first attempt fails as though a commit overwrote the revision being read, so the search
+    // is re-run after reopening, and sees the committed document
+    let mut sizes = Vec::new();
+    let size = reader
+        .retry_search(&enquire, 0, 100, None, |mset| {
+            sizes.push(mset.size());
+            if sizes.len() == 1 {
+                writer.add_document(Document::default())?;
+                writer.commit()?;
+                return Err(Error::DatabaseModified(Default::default()));
+            }
+            Ok(mset.size())
+        })
+        .unwrap();
+    assert_eq!(sizes, [50, 51]);
+    assert_eq!(size, 51);
+}
@@ -0,0 +1,187 @@
+use xapian_rs::{Enquire, Error, Field, Schema, ValueType, WritableDatabase};
+
+mod common;
+
+fn schema() -> Schema {
+    Schema::new()
+        .stemmer("en")
+        .field(Field::text("title", "S").weight(2).stored())
+        .field(Field::text("body", ""))
+        .field(Field::boolean("tag", "K"))
+        .field(Field::value("year", 0, ValueType::Number).stored())
+        .field(Field::value("id", 1, ValueType::Integer))
+}
+
+fn count(db: &WritableDatabase, schema: &Schema, query: &str) -> usize {
+    let mut qp = schema.query_parser().unwrap();
+    let query = qp.parse_query::<&str>(query, None, None).unwrap();
+    let mut enquire = Enquire::new(db);
+    enquire.set_query(&query, None);
+    enquire.mset(0, 10, 10, None).unwrap().matches().count()
+}
+
+#[test]
+fn index_and_search() {
+    let schema = schema();
+    let mut db =
+        WritableDatabase::open(common::scratch("schema-search"), None, None, None, None).unwrap();
+    let mut indexer = schema.term_generator().unwrap();
+
+    let records = [
+        [
+            ("title", "Mantel clocks"),
+            ("body", "Brass"),
+            ("tag", "clock"),
+            ("year", "1890"),
+            ("id", "9007199254740993"),
+        ],
+        [
+            ("title", "Pocket watches"),
+            ("body", "Silver"),
+            ("tag", "watch"),
+            ("year", "1950"),
+            ("id", "9007199254740994"),
+        ],
+    ];
+    for record in records {
+        let doc = schema.index(&mut indexer, record).unwrap();
+        db.add_document(&doc).unwrap();
+    }
+    db.commit().unwrap();
+
+    assert_eq!(count(&db, &schema, "title:clock"), 1);
+    assert_eq!(count(&db, &schema, "silver"), 1);
+    assert_eq!(count(&db, &schema, "tag:watch"), 1);
+    assert_eq!(count(&db, &schema, "year:1800..1900"), 1);
+    assert_eq!(count(&db, &schema, "year:1800..2000"), 2);
+    assert_eq!(count(&db, &schema, "tag:clock year:1900..2000"), 0);
+    assert_eq!(
+        count(&db, &schema, "id:9007199254740993..9007199254740993"),
+        1
+    );
+}
+
+#[test]
+fn boolean_colon() {
+    let schema = Schema::new().field(Field::boolean("category", "XC"));
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = schema.term_generator().unwrap();
+    for category in ["Fiction", "fiction"] {
+        let doc = schema
+            .index(&mut indexer, [("category", category)])
+            .unwrap();
+        db.add_document(&doc).unwrap();
+    }
+
+    assert_eq!(db.termfreq("XC:Fiction").unwrap(), 1);
+    assert_eq!(db.termfreq("XCfiction").unwrap(), 1);
+    assert_eq!(count(&db, &schema, "category:Fiction"), 1);
+    assert_eq!(count(&db, &schema, "category:fiction"), 1);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn timestamps_with_offsets() {
+    let schema = Schema::new().field(Field::value("at", 0, ValueType::DateTime));
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = schema.term_generator().unwrap();
+    for at in [
+        "2024-01-31T12:00:00Z",
+        "2024-01-31T12:00:00+02:00",
+        "2024-01-31T15:00:00",
+    ] {
+        let doc = schema.index(&mut indexer, [("at", at)]).unwrap();
+        db.add_document(&doc).unwrap();
+    }
+
+    assert_eq!(
+        count(&db, &schema, "at:2024-01-31T10:00:00..2024-01-31T12:00:00Z"),
+        2
+    );
+    assert_eq!(
+        count(
+            &db,
+            &schema,
+            "at:2024-01-31T11:00:00+01:00..2024-01-31T15:00:00"
+        ),
+        3
+    );
+}
+
+#[test]
+fn fractional_ranges() {
+    let schema = Schema::new().field(Field::value("price", 0, ValueType::Number));
+    let mut db = WritableDatabase::inmemory();
+    let mut indexer = schema.term_generator().unwrap();
+    for price in ["0.1", "0.7", "1.5"] {
+        let doc = schema.index(&mut indexer, [("price", price)]).unwrap();
+        db.add_document(&doc).unwrap();
+    }
+
+    assert_eq!(count(&db, &schema, "price:0.1..1"), 2);
+    assert_eq!(count(&db, &schema, "price:0.1..0.1"), 1);
+    assert_eq!(count(&db, &schema, "price:..0.7"), 2);
+}
+
+#[test]
+fn stored_fields() {
+    let schema = schema();
+    let mut indexer = schema.term_generator().unwrap();
+    let doc = schema
+        .index(
+            &mut indexer,
+            [
+                ("title", "Mantel clock"),
+                ("body", "Brass"),
+                ("year", "1890"),
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(
+        Schema::stored_fields(&doc).unwrap(),
+        vec![
+            ("title".to_string(), "Mantel clock".to_string()),
+            ("year".to_string(), "1890".to_string()),
+        ]
+    );
+    assert_eq!(doc.value::<f64>(0).unwrap(), Some(Ok(1890.0)));
+    assert!(doc
+        .term("Sclock")
+        .unwrap()
+        .is_some_and(|t| t.wdf().unwrap() == 2));
+}
+
+#[test]
+fn invalid_input() {
+    let schema = schema();
+    let mut indexer = schema.term_generator().unwrap();
+
+    let unknown = schema.index(&mut indexer, [("colour", "red")]);
+    assert!(matches!(unknown, Err(Error::InvalidArgument(_))));
+
+    let not_a_number = schema.index(&mut indexer, [("year", "last year")]);
+    assert!(matches!(not_a_number, Err(Error::InvalidArgument(_))));
+}
+
+#[test]
+fn conflicting_fields() {
+    let duplicate_prefix = Schema::new()
+        .field(Field::text("title", "S"))
+        .field(Field::boolean("subject", "S"));
+    assert!(matches!(
+        duplicate_prefix.validate(),
+        Err(Error::InvalidArgument(_))
+    ));
+
+    let duplicate_slot = Schema::new()
+        .field(Field::value("year", 0, ValueType::Number))
+        .field(Field::text("title", "S").slot(0, ValueType::String));
+    assert!(matches!(
+        duplicate_slot.query_parser(),
+        Err(Error::InvalidArgument(_))
+    ));
+
+    let unprefixed_boolean = Schema::new().field(Field::boolean("tag", ""));
+    assert!(unprefixed_boolean.term_generator().is_err());
+}
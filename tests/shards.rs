@@ -0,0 +1,84 @@
+use xapian_rs::{Database, Enquire, Query};
+
+mod common;
+
+#[test]
+fn search_across_shards() {
+    let museum = common::seed_objects(None);
+    let states = common::seed_states(None);
+    let shards = [&museum, &states];
+
+    let db = Database::from_shards(shards).unwrap();
+    assert_eq!(db.shard_count(), 2);
+    assert_eq!(db.doc_count().unwrap(), 150);
+
+    let mut enquire = Enquire::new(&db);
+    enquire.set_query(Query::match_all(), None);
+    let mset = enquire.mset(0, 150, None, None).unwrap();
+    assert_eq!(mset.size(), 150);
+
+    let mut per_shard = [0; 2];
+    for m in mset.matches() {
+        let index = m.shard_index() as usize;
+        per_shard[index] += 1;
+
+        let shard = shards[index];
+        let expected = shard.document(m.shard_docid()).unwrap().data().unwrap();
+        assert_eq!(m.document().unwrap().data().unwrap(), expected);
+    }
+    assert_eq!(per_shard, [100, 50]);
+}
+
+#[test]
+fn add_database() {
+    let museum = common::seed_objects(None);
+    let states = common::seed_states(None);
+
+    let mut db = museum.read_only();
+    assert_eq!(db.shard_count(), 1);
+
+    db.add_database(&states).unwrap();
+    assert_eq!(db.shard_count(), 2);
+    assert_eq!(db.doc_count().unwrap(), 150);
+
+    // Adding a sharded database adds each of its shards
+    let mut nested = states.read_only();
+    nested.add_database(&db).unwrap();
+    assert_eq!(nested.shard_count(), 3);
+    assert_eq!(nested.doc_count().unwrap(), 200);
+
+    let mut enquire = Enquire::new(&museum);
+    enquire.set_query(Query::match_all(), None);
+    for m in enquire.mset(0, 10, None, None).unwrap().matches() {
+        assert_eq!(m.shard_index(), 0);
+        assert_eq!(m.shard_docid(), m.docid());
+    }
+}
+
+#[test]
+fn stub_database() {
+    let museum_path = common::scratch("shards-stub-objects");
+    let states_path = common::scratch("shards-stub-states");
+    common::seed_objects(museum_path.clone()).commit().unwrap();
+    common::seed_states(states_path.clone()).commit().unwrap();
+
+    let stub = common::scratch("shards-stub");
+    let listing = format!(
+        "auto {}\nauto {}\n",
+        museum_path.display(),
+        states_path.display()
+    );
+    std::fs::write(&stub, listing).unwrap();
+
+    let db = Database::open(&stub, None).unwrap();
+    assert_eq!(db.shard_count(), 2);
+    assert_eq!(db.doc_count().unwrap(), 150);
+
+    let mut enquire = Enquire::new(&db);
+    enquire.set_query(Query::match_all(), None);
+    let mut per_shard = [0; 2];
+    for m in enquire.mset(0, 150, None, None).unwrap().matches() {
+        per_shard[m.shard_index() as usize] += 1;
+    }
+    assert_eq!(per_shard, [100, 50]);
+}
@@ -0,0 +1,67 @@
+use xapian_rs::{QueryParser, QueryParserFlags, WritableDatabase};
+
+mod common;
+
+// In-memory databases have no spelling table, so these tests use an on-disk glass database
+fn seed_spellings(name: &str) -> WritableDatabase {
+    let db = WritableDatabase::open(common::scratch(name), None, None, None, None).unwrap();
+    db.add_spelling("clock", 3).unwrap();
+    db.add_spelling("clack", None).unwrap();
+    db.add_spelling("museum", 2).unwrap();
+    db
+}
+
+#[test]
+fn spelling_suggestion() {
+    let db = seed_spellings("spelling-suggestion");
+    assert_eq!(
+        db.spelling_suggestion("clokc", None).unwrap().as_deref(),
+        Some("clock")
+    );
+    assert_eq!(
+        db.spelling_suggestion("musem", 1).unwrap().as_deref(),
+        Some("museum")
+    );
+    assert_eq!(db.spelling_suggestion("zzzzzz", None).unwrap(), None);
+
+    let ro = db.read_only();
+    assert_eq!(
+        ro.spelling_suggestion("museun", None).unwrap().as_deref(),
+        Some("museum")
+    );
+}
+
+#[test]
+fn spellings() {
+    let db = seed_spellings("spelling-dictionary");
+    db.remove_spelling("clack", None).unwrap();
+
+    let words = db
+        .spellings()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into_iter()
+        .map(|t| (t.to_string(), t.frequency().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        words,
+        [(String::from("clock"), 3), (String::from("museum"), 2)]
+    );
+}
+
+#[test]
+fn corrected_query_string() {
+    let db = seed_spellings("spelling-corrected");
+
+    let mut qp = QueryParser::default();
+    qp.set_database(&db);
+
+    qp.parse_query::<&str>("musem clokc", QueryParserFlags::SPELLING_CORRECTION, None)
+        .unwrap();
+    assert_eq!(qp.corrected_query_string().as_deref(), Some("museum clock"));
+
+    qp.parse_query::<&str>("museum clock", QueryParserFlags::SPELLING_CORRECTION, None)
+        .unwrap();
+    assert_eq!(qp.corrected_query_string(), None);
+}
@@ -0,0 +1,69 @@
+use xapian_rs::{QueryParser, QueryParserFlags, WritableDatabase};
+
+mod common;
+
+// In-memory databases have no synonym table, so these tests use an on-disk glass database
+fn seed_synonyms(name: &str) -> WritableDatabase {
+    let db = WritableDatabase::open(common::scratch(name), None, None, None, None).unwrap();
+    db.add_synonym("clock", "timepiece").unwrap();
+    db.add_synonym("clock", "chronometer").unwrap();
+    db.add_synonym("car", "automobile").unwrap();
+    db
+}
+
+fn strings(iter: impl Iterator<Item = xapian_rs::Result<xapian_rs::Term>>) -> Vec<String> {
+    iter.map(|t| t.unwrap().to_string()).collect()
+}
+
+#[test]
+fn synonyms() {
+    let db = seed_synonyms("synonyms");
+    assert_eq!(
+        strings(db.synonyms("clock").unwrap()),
+        ["chronometer", "timepiece"]
+    );
+    assert_eq!(db.synonyms("watch").unwrap().count(), 0);
+
+    db.remove_synonym("clock", "chronometer").unwrap();
+    assert_eq!(strings(db.synonyms("clock").unwrap()), ["timepiece"]);
+}
+
+#[test]
+fn synonym_keys() {
+    let db = seed_synonyms("synonym-keys");
+    assert_eq!(strings(db.synonym_keys("").unwrap()), ["car", "clock"]);
+    assert_eq!(strings(db.synonym_keys("cl").unwrap()), ["clock"]);
+
+    let ro = db.read_only();
+    assert_eq!(strings(ro.synonym_keys("ca").unwrap()), ["car"]);
+    assert_eq!(strings(ro.synonyms("car").unwrap()), ["automobile"]);
+}
+
+#[test]
+fn query_parser_synonyms() {
+    let db = seed_synonyms("synonym-query");
+
+    let mut qp = QueryParser::default();
+    qp.set_database(&db);
+
+    let query = qp
+        .parse_query::<&str>(
+            "~clock",
+            QueryParserFlags::DEFAULT | QueryParserFlags::SYNONYM,
+            None,
+        )
+        .unwrap();
+    let terms = strings(query.terms());
+    assert!(terms.contains(&String::from("timepiece")), "{query}");
+
+    let query = qp
+        .parse_query::<&str>("car", QueryParserFlags::AUTO_SYNONYMS, None)
+        .unwrap();
+    let terms = strings(query.terms());
+    assert!(terms.contains(&String::from("automobile")), "{query}");
+
+    let query = qp
+        .parse_query::<&str>("car", QueryParserFlags::DEFAULT, None)
+        .unwrap();
+    assert_eq!(strings(query.terms()), ["car"]);
+}
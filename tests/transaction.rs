@@ -0,0 +1,61 @@
+use std::panic::AssertUnwindSafe;
+
+use xapian_rs::{Document, Error, WritableDatabase};
+
+mod common;
+
+#[test]
+fn commit_guard() {
+    let mut db =
+        WritableDatabase::open(common::scratch("tx-commit"), None, None, None, None).unwrap();
+    let mut tx = db.begin().unwrap();
+    tx.add_document(Document::default()).unwrap();
+    tx.commit().unwrap();
+    assert_eq!(db.doc_count().unwrap(), 1);
+}
+
+#[test]
+fn cancel_on_drop() {
+    let mut db =
+        WritableDatabase::open(common::scratch("tx-drop"), None, None, None, None).unwrap();
+    {
+        let mut tx = db.begin().unwrap();
+        tx.add_document(Document::default()).unwrap();
+    }
+    assert_eq!(db.doc_count().unwrap(), 0);
+
+    // The cancelled transaction no longer blocks a new one
+    db.begin().unwrap().commit().unwrap();
+}
+
+#[test]
+fn cancel_on_panic() {
+    let mut db =
+        WritableDatabase::open(common::scratch("tx-panic"), None, None, None, None).unwrap();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        db.transaction(None, |tx| {
+            tx.add_document(Document::default()).unwrap();
+            panic!("abort indexing");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(db.doc_count().unwrap(), 0);
+}
+
+#[test]
+fn try_transaction() {
+    let mut db = WritableDatabase::open(common::scratch("tx-try"), None, None, None, None).unwrap();
+    let id = db
+        .try_transaction(|tx| tx.add_document(Document::default()))
+        .unwrap();
+    assert!(db.has_document(id).unwrap());
+
+    let result: Result<(), Error> = db.try_transaction(|tx| {
+        tx.add_document(Document::default())?;
+        tx.delete_document(id)?;
+        tx.document(id).map(drop)
+    });
+    assert!(matches!(result, Err(Error::DocNotFound(_))));
+    assert_eq!(db.doc_count().unwrap(), 1);
+    assert!(db.has_document(id).unwrap());
+}
@@ -0,0 +1,125 @@
+use bytes::Bytes;
+use xapian_rs::{
+    Document, Enquire, FromValue, IntegerRangeProcessor, QueryParser, ToValue,
+    UnsignedRangeProcessor, WritableDatabase,
+};
+
+fn round_trip<T: ToValue + FromValue + std::fmt::Debug>(value: T) -> T {
+    T::deserialize(value.serialize()).unwrap()
+}
+
+#[test]
+fn bool_and_char() {
+    assert!(round_trip(true));
+    assert!(!round_trip(false));
+    assert!(false.serialize() < true.serialize());
+    assert!(bool::deserialize(Bytes::from_static(b"yes")).is_err());
+
+    assert_eq!(round_trip('x'), 'x');
+    assert_eq!(round_trip('é'), 'é');
+    assert!('a'.serialize() < 'é'.serialize());
+    assert!(char::deserialize(Bytes::from_static(b"ab")).is_err());
+}
+
+#[test]
+fn options() {
+    assert!(Option::<u32>::None.serialize().is_empty());
+    assert_eq!(round_trip(Some(7u32)), Some(7));
+    assert_eq!(round_trip(Option::<String>::None), None);
+
+    // Empty `Some` values can't be told apart from `None`
+    assert_eq!(round_trip(Some(String::new())), None);
+    assert_eq!(round_trip(Some(Option::<u32>::None)), None);
+
+    let mut doc = Document::default();
+    doc.set_value(0, Some("present")).unwrap();
+    doc.set_value(1, Option::<&str>::None).unwrap();
+    assert_eq!(
+        doc.value::<String>(0).unwrap(),
+        Some(Ok("present".to_string()))
+    );
+    assert_eq!(doc.values_count().unwrap(), 1);
+}
+
+#[test]
+fn lossless_integers() {
+    let big = (1u64 << 53) + 1;
+    assert_eq!(round_trip(big), big);
+    assert_eq!(round_trip(u64::MAX), u64::MAX);
+    assert_eq!(round_trip(i64::MIN), i64::MIN);
+    assert_eq!(round_trip(-(1i64 << 53) - 1), -(1i64 << 53) - 1);
+    assert_eq!(round_trip(usize::MAX), usize::MAX);
+
+    let signed = [i64::MIN, -(1 << 60), -1, 0, 1, 1 << 60, i64::MAX];
+    assert!(signed
+        .windows(2)
+        .all(|w| w[0].serialize() < w[1].serialize()));
+    let unsigned = [0, 1, 255, 256, 1 << 53, (1 << 53) + 1, u64::MAX];
+    assert!(unsigned
+        .windows(2)
+        .all(|w| w[0].serialize() < w[1].serialize()));
+
+    assert!(u64::deserialize(Bytes::from_static(b"short")).is_err());
+}
+
+#[test]
+fn integer_ranges() {
+    let mut db = WritableDatabase::inmemory();
+    for n in [-(1i64 << 60), -5, 3, 10, (1 << 53) + 1] {
+        let mut doc = Document::default();
+        doc.set_value(0, n).unwrap();
+        doc.set_value(1, n.unsigned_abs()).unwrap();
+        db.add_document(&doc).unwrap();
+    }
+    db.commit().unwrap();
+
+    let mut qp = QueryParser::default();
+    qp.add_rangeprocessor("n:", 0, IntegerRangeProcessor, false, false, None);
+    qp.add_rangeprocessor("abs:", 1, UnsignedRangeProcessor, false, false, None);
+
+    let mut count = |query: &str| {
+        let query = qp.parse_query::<&str>(query, None, None).unwrap();
+        let mut enquire = Enquire::new(&db);
+        enquire.set_query(&query, None);
+        enquire.mset(0, 10, 10, None).unwrap().matches().count()
+    };
+
+    assert_eq!(count("n:-10..10"), 3);
+    assert_eq!(count("n:1..10"), 2);
+    assert_eq!(count("n:9007199254740993..9007199254740993"), 1);
+    assert_eq!(count("abs:1..5"), 2);
+    assert_eq!(count("abs:9007199254740993.."), 2);
+}
+
+#[test]
+fn composite_values() {
+    let value = ("books".to_string(), 12.5f64, Some(3u8));
+    assert_eq!(round_trip(value.clone()), value);
+
+    // Components containing zero bytes are escaped
+    let binary = (Bytes::from_static(b"a\0b"), Bytes::from_static(b"\0"));
+    assert_eq!(round_trip(binary.clone()), binary);
+
+    let sorted = [
+        ("", 100.0),
+        ("book", -1.0),
+        ("book", 9.99),
+        ("book", 10.0),
+        ("books", 0.0),
+        ("toys", 0.5),
+    ];
+    assert!(sorted
+        .windows(2)
+        .all(|w| w[0].serialize() < w[1].serialize()));
+
+    assert!(<(String, String)>::deserialize(Bytes::from_static(b"no separator")).is_err());
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuids() {
+    let id = uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+    assert_eq!(round_trip(id), id);
+    assert!(uuid::Uuid::nil().serialize() < id.serialize());
+    assert!(uuid::Uuid::deserialize(Bytes::from_static(b"too short")).is_err());
+}
@@ -0,0 +1,269 @@
+#![warn(missing_docs)]
+//! Derive macros for [`xapian-rs`](https://docs.rs/xapian-rs)
+//!
+//! These are re-exported by `xapian-rs` when its `derive` feature is enabled, and should be used
+//! from there rather than by depending on this crate directly
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitInt, LitStr};
+
+/// Derive `IntoDocument` and `FromDocument` for a struct with named fields
+///
+/// Each field may be annotated with `#[xapian(...)]`, containing one or more of:
+/// - `text`: index the field (which must implement `AsRef<str>`) as free text, using the
+///   `TermGenerator` passed to `into_document`. May be combined with `prefix = "..."` and
+///   `weight = N` (the within-document-frequency increment)
+/// - `boolean`: add the field (which must implement `Display` and `FromStr`) as a boolean term,
+///   with `prefix = "..."`, which is required so that the term can be found again. The term is
+///   built by `xapian_rs::boolean_term`, matching how `QueryParser` handles boolean prefixes
+/// - `id`: add the field (which must implement `Display` and `FromStr`) as the document's unique
+///   boolean term, with `prefix = "..."` (`"Q"` by default), and return that term from
+///   `IntoDocument::unique_term`
+/// - `value = N`: store the field in value slot `N` via `ToValue` / `FromValue`
+/// - `data`: store the field as the document data via `ToValue` / `FromValue`
+///
+/// When converting a document back into a struct, fields are restored from their value slot, the
+/// document data or their boolean term (in that order of preference). Fields which can't be
+/// restored, such as those which are only indexed as text, are set to `Default::default()`.
+/// Since a boolean term is found by its prefix, that prefix can't be the start of another field's
+/// prefix, nor start with `Z` (used for stemmed words) when the struct has a `text` field
+#[proc_macro_derive(XapianDocument, attributes(xapian))]
+pub fn derive_xapian_document(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    text: bool,
+    boolean: bool,
+    id: bool,
+    data: bool,
+    value: Option<LitInt>,
+    prefix: Option<LitStr>,
+    weight: Option<LitInt>,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("xapian")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("text") {
+                    attrs.text = true;
+                } else if meta.path.is_ident("boolean") {
+                    attrs.boolean = true;
+                } else if meta.path.is_ident("id") {
+                    attrs.id = true;
+                } else if meta.path.is_ident("data") {
+                    attrs.data = true;
+                } else if meta.path.is_ident("value") {
+                    attrs.value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("prefix") {
+                    attrs.prefix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("weight") {
+                    attrs.weight = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported xapian attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let terms = [attrs.text, attrs.boolean, attrs.id]
+            .into_iter()
+            .filter(|x| *x)
+            .count();
+        if terms > 1 {
+            return Err(Error::new_spanned(
+                field,
+                "only one of `text`, `boolean` or `id` may be used on a field",
+            ));
+        }
+        if attrs.prefix.is_some() && terms == 0 {
+            return Err(Error::new_spanned(
+                field,
+                "`prefix` requires one of `text`, `boolean` or `id`",
+            ));
+        }
+        // Boolean terms are restored by their prefix, so it must be able to tell them apart
+        let prefixed = attrs
+            .prefix
+            .as_ref()
+            .map_or(attrs.id, |p| !p.value().is_empty());
+        if (attrs.boolean || attrs.id) && !prefixed {
+            return Err(Error::new_spanned(
+                field,
+                "`boolean` and `id` require a non-empty `prefix`",
+            ));
+        }
+        if attrs.weight.is_some() && !attrs.text {
+            return Err(Error::new_spanned(field, "`weight` requires `text`"));
+        }
+        Ok(attrs)
+    }
+
+    fn prefix(&self) -> LitStr {
+        self.prefix
+            .clone()
+            .unwrap_or_else(|| LitStr::new(if self.id { "Q" } else { "" }, Span::call_site()))
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "XapianDocument can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "XapianDocument can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut index = Vec::new();
+    let mut restore = Vec::new();
+    let mut unique_term = None;
+    let mut has_data = false;
+    let mut has_text = false;
+    let mut prefixes = Vec::new();
+
+    for field in fields {
+        let attrs = FieldAttrs::parse(field)?;
+        let ident: &Ident = field.ident.as_ref().unwrap();
+        let name = ident.to_string();
+        let prefix = attrs.prefix();
+        if attrs.text || attrs.boolean || attrs.id {
+            prefixes.push((field, prefix.value(), attrs.boolean || attrs.id));
+        }
+
+        if attrs.text {
+            let weight = match &attrs.weight {
+                Some(weight) => quote!(Some(#weight)),
+                None => quote!(None),
+            };
+            // Keep phrases from spanning separate fields
+            if has_text {
+                index.push(quote!(indexer.increase_termpos(None);));
+            }
+            has_text = true;
+            index.push(quote! {
+                indexer.index_text(&self.#ident, #weight, Some(#prefix))?;
+            });
+        }
+
+        if attrs.boolean || attrs.id {
+            index.push(quote! {
+                doc.add_boolean_term(::xapian_rs::boolean_term(
+                    #prefix,
+                    &::std::string::ToString::to_string(&self.#ident),
+                ))?;
+            });
+        }
+
+        if attrs.id {
+            if unique_term.is_some() {
+                return Err(Error::new_spanned(field, "only one field may use `id`"));
+            }
+            unique_term = Some(quote! {
+                fn unique_term(&self) -> Option<String> {
+                    Some(::xapian_rs::boolean_term(
+                        #prefix,
+                        &::std::string::ToString::to_string(&self.#ident),
+                    ))
+                }
+            });
+        }
+
+        if let Some(slot) = &attrs.value {
+            index.push(quote! {
+                doc.set_value(#slot, ::xapian_rs::ToValue::serialize(&self.#ident))?;
+            });
+        }
+
+        if attrs.data {
+            if has_data {
+                return Err(Error::new_spanned(field, "only one field may use `data`"));
+            }
+            has_data = true;
+            index.push(quote! {
+                doc.set_data(::xapian_rs::ToValue::serialize(&self.#ident));
+            });
+        }
+
+        let value = if let Some(slot) = &attrs.value {
+            quote!(::xapian_rs::__private::value(doc, #slot, #name)?)
+        } else if attrs.data {
+            quote!(::xapian_rs::__private::data(doc, #name)?)
+        } else if attrs.boolean || attrs.id {
+            quote!(::xapian_rs::__private::term(doc, #prefix, #name)?)
+        } else {
+            quote!(::core::default::Default::default())
+        };
+        restore.push(quote!(#ident: #value));
+    }
+
+    // A boolean term is restored from the first term starting with its prefix, which would also
+    // match the terms of any field whose prefix starts with the same characters
+    for (i, (field, prefix, boolean)) in prefixes.iter().enumerate() {
+        let clash = prefixes
+            .iter()
+            .enumerate()
+            .find(|(j, (_, other, _))| *boolean && i != *j && other.starts_with(prefix.as_str()));
+        if let Some((_, (other, _, _))) = clash {
+            return Err(Error::new_spanned(
+                field,
+                format!(
+                    "prefix `{prefix}` is also the start of the prefix of `{}`",
+                    other.ident.as_ref().unwrap()
+                ),
+            ));
+        }
+        // The stemmed forms of words in `text` fields are indexed with a `Z` prefix
+        if *boolean && has_text && prefix.starts_with('Z') {
+            return Err(Error::new_spanned(
+                field,
+                format!("prefix `{prefix}` clashes with the `Z` prefix of stemmed `text` terms"),
+            ));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::xapian_rs::IntoDocument for #ident #ty_generics #where_clause {
+            fn into_document(
+                self,
+                indexer: &mut ::xapian_rs::TermGenerator,
+            ) -> ::xapian_rs::Result<::xapian_rs::Document> {
+                #[allow(unused_mut)]
+                let mut doc = ::xapian_rs::Document::default();
+                indexer.set_document(&doc);
+                #(#index)*
+                Ok(doc)
+            }
+
+            #unique_term
+        }
+
+        impl #impl_generics ::xapian_rs::FromDocument for #ident #ty_generics #where_clause {
+            fn from_document(doc: &::xapian_rs::Document) -> ::xapian_rs::Result<Self> {
+                Ok(Self {
+                    #(#restore,)*
+                })
+            }
+        }
+    })
+}